@@ -0,0 +1,11 @@
+#!/usr/bin/env bash
+
+set -euxo pipefail
+
+export RUST_BACKTRACE=1
+
+cd chrono-tz/tests/check-list-filtering
+
+export CHRONO_TZ_TIMEZONE_LIST="$PWD/zones.txt"
+
+cargo test --color=always -- --color=always
@@ -0,0 +1,16 @@
+#!/usr/bin/env bash
+
+set -euxo pipefail
+
+export RUST_BACKTRACE=1
+export CHRONO_TZ_YEARS='2000..2040'
+
+cd chrono-tz
+
+# Zones that are left with the same timespans share their tables.
+cargo test --features filter-by-years --lib --color=always -- \
+    --color=always identical_timespans_share_tables
+
+cd tests/check-year-range
+
+cargo test --color=always -- --color=always
@@ -0,0 +1,93 @@
+//! Writing a tzdb bundle: every zone of the database as TZif data in a single file,
+//! which `chrono_tz::runtime::Database` reads.
+//!
+//! All numbers are big-endian, as in TZif files, and strings are UTF-8 preceded by
+//! their length as one byte. A bundle is laid out as:
+//!
+//! 1. The magic `TZDB` and the format version `1`;
+//! 2. The version of the tz database, as a string;
+//! 3. The number of zones as a `u32`, then for each zone its name, the length of its
+//!    TZif data as a `u32`, and the data;
+//! 4. The number of links as a `u32`, then for each link its name and the name of the
+//!    zone that it points to;
+//! 5. The number of countries as a `u32`, which may be zero, then for each country its
+//!    code, its name, the number of its zones as a `u16`, and their names.
+
+use std::io::{self, Write};
+
+use parse_zoneinfo::table::Table;
+use parse_zoneinfo::transitions::TableTransitions;
+
+use crate::tzif::write_tzif;
+use crate::Tabs;
+
+/// The format version of the bundles that this module writes.
+const FORMAT_VERSION: u8 = 1;
+
+/// Writes the bundle of `table`, with the country tables of `tabs` if there are any.
+pub(crate) fn write_bundle(
+    out: &mut impl Write,
+    table: &Table,
+    version: &str,
+    tabs: Option<&Tabs>,
+) -> io::Result<()> {
+    out.write_all(b"TZDB")?;
+    out.write_all(&[FORMAT_VERSION])?;
+    write_str(out, version)?;
+
+    write_len(out, table.zonesets.len())?;
+    for zone in table.zonesets.keys() {
+        let timespans = table.timespans(zone).unwrap();
+        let extra = table.extra_rule(zone);
+        let mut data = Vec::new();
+        write_tzif(&mut data, &timespans, extra.as_ref())?;
+        write_str(out, zone)?;
+        write_len(out, data.len())?;
+        out.write_all(&data)?;
+    }
+
+    write_len(out, table.links.len())?;
+    for (link, target) in &table.links {
+        // Links may point to other links, but readers only have to look up zones.
+        let mut target = target;
+        while let Some(next) = table.links.get(target) {
+            target = next;
+        }
+        write_str(out, link)?;
+        write_str(out, target)?;
+    }
+
+    let Some(tabs) = tabs else {
+        return write_len(out, 0);
+    };
+    let zones = table.zonesets.keys().chain(table.links.keys()).collect();
+    let zone_tab = tabs.zone_entries(&zones);
+    let countries = tabs.countries();
+    write_len(out, countries.len())?;
+    for country in countries {
+        let country_zones = zone_tab
+            .iter()
+            .filter(|(_, entry)| entry.countries[0] == country.code)
+            .map(|&(zone, _)| zone)
+            .collect::<Vec<_>>();
+        write_str(out, country.code)?;
+        write_str(out, country.name)?;
+        let len = u16::try_from(country_zones.len()).expect("too many zones in a country");
+        out.write_all(&len.to_be_bytes())?;
+        for zone in country_zones {
+            write_str(out, zone)?;
+        }
+    }
+    Ok(())
+}
+
+fn write_len(out: &mut impl Write, len: usize) -> io::Result<()> {
+    let len = u32::try_from(len).expect("too many entries");
+    out.write_all(&len.to_be_bytes())
+}
+
+fn write_str(out: &mut impl Write, s: &str) -> io::Result<()> {
+    let len = u8::try_from(s.len()).unwrap_or_else(|_| panic!("{s:?} is too long"));
+    out.write_all(&[len])?;
+    out.write_all(s.as_bytes())
+}
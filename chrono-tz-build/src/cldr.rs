@@ -0,0 +1,230 @@
+//! Reading the files of the Unicode CLDR that are vendored in `cldr/`.
+//!
+//! Most of these come from the JSON packages of CLDR. `windowsZones.xml` is only
+//! published as XML, but as regular XML: one element per line with double-quoted
+//! attributes. That is all this reads of it, rather than pulling a full XML parser
+//! into every build of chrono-tz. Likewise, only the `metazones` feature parses the
+//! JSON files as such; every build needs a few objects of them that only have string
+//! members, which are read the same way.
+
+#[cfg(feature = "metazones")]
+use std::collections::BTreeMap;
+use std::collections::{BTreeSet, HashMap};
+
+#[cfg(feature = "metazones")]
+use serde_json::Value;
+
+/// A `mapZone` element of `windowsZones.xml`, which maps a Windows time zone ID in
+/// a territory to IANA zone names.
+pub(crate) struct MapZone<'a> {
+    /// The Windows time zone ID, such as `Pacific Standard Time`.
+    pub(crate) windows_id: &'a str,
+    /// The ISO 3166 code of the territory, or `001` for the default zone of the ID
+    /// and `ZZ` for the fixed offset that it corresponds to.
+    pub(crate) territory: &'a str,
+    /// The IANA zone names, with the most populous one first.
+    pub(crate) zones: Vec<&'a str>,
+}
+
+/// The `mapZone` elements of `windowsZones.xml`, in the order of the file.
+pub(crate) fn windows_zones(xml: &str) -> Vec<MapZone<'_>> {
+    elements(xml, "mapZone")
+        .map(|element| MapZone {
+            windows_id: element.attribute("other").expect("mapZone without other"),
+            territory: element
+                .attribute("territory")
+                .expect("mapZone without territory"),
+            zones: element
+                .attribute("type")
+                .expect("mapZone without type")
+                .split_ascii_whitespace()
+                .collect(),
+        })
+        .collect()
+}
+
+/// An XML element, without its content.
+pub(crate) struct Element<'a>(&'a str);
+
+impl<'a> Element<'a> {
+    /// The value of an attribute. Entities are not expanded, as CLDR doesn't use any
+    /// in the attributes that we read.
+    pub(crate) fn attribute(&self, name: &str) -> Option<&'a str> {
+        let mut rest = self.0;
+        while let Some(start) = rest.find(name) {
+            let preceded_by_space = rest[..start].ends_with(char::is_whitespace);
+            rest = &rest[start + name.len()..];
+            if let (true, Some(value)) = (preceded_by_space, rest.strip_prefix("=\"")) {
+                return value.split_once('"').map(|(value, _)| value);
+            }
+        }
+        None
+    }
+}
+
+/// The elements named `name` in `xml`, outside of comments.
+pub(crate) fn elements<'a>(xml: &'a str, name: &'a str) -> impl Iterator<Item = Element<'a>> {
+    let mut in_comment = false;
+    xml.lines().filter_map(move |line| {
+        let line = line.trim();
+        if in_comment || line.starts_with("<!--") {
+            in_comment = !line.ends_with("-->");
+            return None;
+        }
+        let tag = line.strip_prefix('<')?.strip_prefix(name)?;
+        match tag.starts_with(char::is_whitespace) {
+            true => Some(Element(tag)),
+            false => None,
+        }
+    })
+}
+
+/// The canonical CLDR name of each IANA name from `bcp47/timezone.json`, such as
+/// `Asia/Calcutta` for `Asia/Kolkata`.
+///
+/// CLDR keys all of its data by these names, which are often older than the ones
+/// the tz database uses now.
+pub(crate) fn canonical_names(json: &str) -> HashMap<String, String> {
+    let mut names = HashMap::new();
+    for key in flat_objects(json) {
+        let mut aliases = match string_member(key, "_alias") {
+            Some(aliases) => aliases.split_ascii_whitespace(),
+            None => continue,
+        };
+        let canonical = aliases.next().unwrap_or_default();
+        for alias in aliases.chain(string_member(key, "_iana")) {
+            names.insert(alias.to_owned(), canonical.to_owned());
+        }
+    }
+    names
+}
+
+/// A period of `supplemental/metaZones.json` during which a zone uses a metazone.
+#[cfg(feature = "metazones")]
+pub(crate) struct MetazonePeriod {
+    /// The name of the metazone, such as `Europe_Central`.
+    pub(crate) metazone: String,
+    /// When the period starts, as a Unix timestamp.
+    pub(crate) from: Option<i64>,
+    /// When the period ends, as a Unix timestamp.
+    pub(crate) to: Option<i64>,
+}
+
+/// The metazones of each zone over time from `supplemental/metaZones.json`, by the
+/// canonical CLDR name of the zone, sorted by the start of the period.
+#[cfg(feature = "metazones")]
+pub(crate) fn metazone_periods(json: &str) -> BTreeMap<String, Vec<MetazonePeriod>> {
+    let json = serde_json::from_str::<Value>(json).expect("invalid metaZones.json");
+    let mut periods = BTreeMap::new();
+    for (zone, value) in leaves(&json["supplemental"]["metaZones"]["metazoneInfo"]["timezone"]) {
+        let value = value.as_array().expect("invalid metazone periods");
+        let mut zone_periods = value
+            .iter()
+            .map(|period| {
+                let period = &period["usesMetazone"];
+                let timestamp = |key: &str| period[key].as_str().map(timestamp);
+                MetazonePeriod {
+                    metazone: period["_mzone"]
+                        .as_str()
+                        .expect("metazone period without metazone")
+                        .to_owned(),
+                    from: timestamp("_from"),
+                    to: timestamp("_to"),
+                }
+            })
+            .collect::<Vec<_>>();
+        zone_periods.sort_by_key(|period| period.from);
+        periods.insert(zone, zone_periods);
+    }
+    periods
+}
+
+/// The CLDR names of the zones that represent a metazone in `supplemental/metaZones.json`,
+/// such as `America/New_York` for `America_Eastern`: those it maps the metazone to in
+/// territory `001`.
+pub(crate) fn golden_zones(json: &str) -> BTreeSet<String> {
+    flat_objects(json)
+        .filter(|map_zone| string_member(map_zone, "_territory") == Some("001"))
+        .filter_map(|map_zone| string_member(map_zone, "_type"))
+        .map(str::to_owned)
+        .collect()
+}
+
+/// The contents of the JSON objects in `json` that contain no other objects.
+fn flat_objects(json: &str) -> impl Iterator<Item = &str> {
+    json.split('{')
+        .filter_map(|object| object.split_once('}').map(|(object, _)| object))
+}
+
+/// The value of the member `key` of a flat JSON object, if it is a string without
+/// escapes.
+fn string_member<'a>(object: &'a str, key: &str) -> Option<&'a str> {
+    let (_, rest) = object.split_once(&format!("\"{key}\""))?;
+    let value = rest.trim_start().strip_prefix(':')?.trim_start();
+    value
+        .strip_prefix('"')?
+        .split_once('"')
+        .map(|(value, _)| value)
+}
+
+/// The values of a tree of objects keyed by the parts of zone names, such as
+/// `{"America": {"Indiana": {"Knox": value}}}`, with their full zone names.
+///
+/// A value is anything that isn't an object, or an object with one of the keys that
+/// CLDR uses for the data of a zone.
+#[cfg(feature = "metazones")]
+pub(crate) fn leaves(tree: &Value) -> Vec<(String, &Value)> {
+    fn is_leaf(value: &Value) -> bool {
+        match value.as_object() {
+            Some(object) => ["exemplarCity", "long", "short"]
+                .iter()
+                .any(|key| object.contains_key(*key)),
+            None => true,
+        }
+    }
+
+    let mut leaves = Vec::new();
+    let mut stack = vec![(String::new(), tree)];
+    while let Some((prefix, value)) = stack.pop() {
+        if !prefix.is_empty() && is_leaf(value) {
+            leaves.push((prefix, value));
+            continue;
+        }
+        for (key, value) in value.as_object().into_iter().flatten() {
+            let name = match prefix.is_empty() {
+                true => key.clone(),
+                false => format!("{prefix}/{key}"),
+            };
+            stack.push((name, value));
+        }
+    }
+    leaves.sort_by(|a, b| a.0.cmp(&b.0));
+    leaves
+}
+
+/// Converts a date of `supplemental/metaZones.json`, such as `1971-10-31 02:00` in
+/// UTC, to a Unix timestamp.
+#[cfg(feature = "metazones")]
+fn timestamp(date: &str) -> i64 {
+    let fields = date
+        .split(['-', ' ', ':'])
+        .map(str::parse::<i64>)
+        .collect::<Result<Vec<_>, _>>();
+    let (year, month, day, hour, minute) = match fields.as_deref() {
+        Ok(&[year, month, day, hour, minute]) => (year, month, day, hour, minute),
+        _ => panic!("invalid date in metaZones.json: {date}"),
+    };
+
+    // The number of days since 1970-01-01 of the proleptic Gregorian calendar,
+    // counting years from March so that leap days come last.
+    let (year, month) = match month <= 2 {
+        true => (year - 1, month + 9),
+        false => (year, month - 3),
+    };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let day_of_year = (153 * month + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146_097 + day_of_era - 719_468;
+    days * 86_400 + hour * 3600 + minute * 60
+}
@@ -0,0 +1,114 @@
+//! Writing the timespans of a zone in the compact encoding that `chrono-tz` reads in
+//! its `compact` module.
+//!
+//! Each distinct timespan is written once, and transitions refer to them by index.
+//! The transition times are written as differences from the previous one, except
+//! for the first transition of every block, whose time is written in full.
+
+use std::fmt::Write;
+
+use parse_zoneinfo::transitions::{FixedTimespan, FixedTimespanSet};
+
+/// The number of transitions in a block, which `chrono-tz` has to agree with.
+const BLOCK: usize = 16;
+
+/// The tables of a zone, as the fields of a `CompactTimespans`.
+pub(crate) struct Compact<'a> {
+    types: Vec<&'a FixedTimespan>,
+    indices: Vec<u8>,
+    deltas: Vec<u8>,
+    blocks: Vec<(i64, u16)>,
+}
+
+impl<'a> Compact<'a> {
+    pub(crate) fn new(timespans: &'a FixedTimespanSet) -> Self {
+        let mut compact = Compact {
+            types: vec![&timespans.first],
+            indices: Vec::new(),
+            deltas: Vec::new(),
+            blocks: Vec::new(),
+        };
+
+        let mut previous = i64::MIN;
+        for (i, (at, timespan)) in timespans.rest.iter().enumerate() {
+            let index = match compact.types.iter().position(|&known| known == timespan) {
+                Some(index) => index,
+                None => {
+                    compact.types.push(timespan);
+                    compact.types.len() - 1
+                }
+            };
+            compact
+                .indices
+                .push(u8::try_from(index).expect("too many timespans"));
+
+            if i % BLOCK == 0 {
+                let start = u16::try_from(compact.deltas.len()).expect("too many transitions");
+                compact.blocks.push((*at, start));
+            } else {
+                let delta = at - previous;
+                assert!(delta > 0, "transitions out of order at {at}");
+                // The lowest bit says whether the rest counts seconds or hours.
+                let value = match delta % 3600 {
+                    0 => (delta / 3600) << 1,
+                    _ => delta << 1 | 1,
+                };
+                write_varint(&mut compact.deltas, value as u64);
+            }
+            previous = *at;
+        }
+        compact
+    }
+
+    /// Writes the tables as the fields of a struct literal, indented for a `static`
+    /// in a function.
+    pub(crate) fn write_fields(&self, out: &mut String) {
+        out.push_str("            types: &[\n");
+        for timespan in &self.types {
+            writeln!(
+                out,
+                "                FixedTimespan {{ utc_offset: {}, dst_offset: {}, name: Some({:?}) }},",
+                timespan.utc_offset, timespan.dst_offset, timespan.name
+            )
+            .unwrap();
+        }
+        out.push_str("            ],\n");
+        write!(out, "            indices: ").unwrap();
+        write_bytes(out, &self.indices);
+        write!(out, ",\n            deltas: ").unwrap();
+        write_bytes(out, &self.deltas);
+        out.push_str(",\n            blocks: &[");
+        for (i, (at, start)) in self.blocks.iter().enumerate() {
+            if i % 4 == 0 {
+                out.push_str("\n               ");
+            }
+            write!(out, " ({at}, {start}),").unwrap();
+        }
+        out.push_str(match self.blocks.is_empty() {
+            true => "],\n",
+            false => "\n            ],\n",
+        });
+    }
+}
+
+/// Writes `value` in little-endian base 128, with the high bit of each byte set when
+/// more bytes follow.
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+/// Writes `bytes` as a byte string literal, with 32 bytes to a line.
+fn write_bytes(out: &mut String, bytes: &[u8]) {
+    out.push_str("b\"");
+    for (i, byte) in bytes.iter().enumerate() {
+        if i > 0 && i % 32 == 0 {
+            out.push_str("\\\n                ");
+        }
+        write!(out, "\\x{byte:02x}").unwrap();
+    }
+    out.push('"');
+}
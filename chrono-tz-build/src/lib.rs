@@ -2,23 +2,58 @@ extern crate parse_zoneinfo;
 #[cfg(feature = "filter-by-regex")]
 extern crate regex;
 
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::env;
-use std::fs::File;
-use std::io::{self, BufRead, BufReader, Write};
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
 use std::path::{Path, PathBuf};
 
 use parse_zoneinfo::line::Line;
 use parse_zoneinfo::structure::{Child, Structure};
+use parse_zoneinfo::tab::{Country, ZoneTabEntry};
 use parse_zoneinfo::table::{Table, TableBuilder};
-use parse_zoneinfo::transitions::FixedTimespan;
 use parse_zoneinfo::transitions::TableTransitions;
+use parse_zoneinfo::transitions::{ExtraRule, FixedTimespan, RuleDate, RuleTransition};
 use parse_zoneinfo::FILES;
 
+use crate::cldr::MapZone;
+use crate::compact::Compact;
+
+mod bundle;
+mod cldr;
+mod compact;
+#[cfg(feature = "metazones")]
+mod metazones;
+#[cfg(feature = "localized-names")]
+mod names;
+mod tzif;
+#[cfg(feature = "filter-by-years")]
+mod years;
+
+#[cfg(feature = "metazones")]
+pub use crate::metazones::write_metazones_file;
+#[cfg(feature = "localized-names")]
+pub use crate::names::write_names_file;
+
 /// The name of the environment variable which possibly holds the filter regex.
 #[cfg(feature = "filter-by-regex")]
 pub const FILTER_ENV_VAR_NAME: &str = "CHRONO_TZ_TIMEZONE_FILTER";
 
+/// The name of the environment variable which possibly holds the path to a file
+/// listing the zones to keep, one per line.
+#[cfg(feature = "filter-by-list")]
+pub const ZONE_LIST_ENV_VAR_NAME: &str = "CHRONO_TZ_TIMEZONE_LIST";
+
+/// The name of the environment variable which possibly holds the range of years
+/// to keep the transitions of, such as `2000..2040`.
+#[cfg(feature = "filter-by-years")]
+pub const YEARS_ENV_VAR_NAME: &str = "CHRONO_TZ_YEARS";
+
+/// The name of the environment variable which possibly holds the comma-separated
+/// list of locales to generate localized names for.
+#[cfg(feature = "localized-names")]
+pub const LOCALES_ENV_VAR_NAME: &str = "CHRONO_TZ_LOCALES";
+
 // This function is needed until zoneinfo_parse handles comments correctly.
 // Technically a '#' symbol could occur between double quotes and should be
 // ignored in this case, however this never happens in the tz database as it
@@ -30,27 +65,56 @@ fn strip_comments(mut line: String) -> String {
     line
 }
 
-// Generate a list of the time zone periods beyond the first that apply
-// to this zone, as a string representation of a static slice.
-fn format_rest(rest: Vec<(i64, FixedTimespan)>) -> String {
-    let mut ret = "&[\n".to_string();
-    for (
-        start,
-        FixedTimespan {
-            utc_offset,
-            dst_offset,
-            name,
-        },
-    ) in rest
-    {
-        ret.push_str(&format!(
-            "                ({start}, FixedTimespan {{ \
-             utc_offset: {utc_offset}, dst_offset: {dst_offset}, name: Some({name:?}) \
-             }}),\n",
-        ));
-    }
-    ret.push_str("            ]");
-    ret
+// Generate the rule that applies after the last transition of this zone. Zones
+// without daylight saving time at the end don't need one, as their last
+// timespan already lasts forever.
+fn format_extra(extra: Option<ExtraRule>) -> String {
+    fn format_timespan(timespan: &FixedTimespan) -> String {
+        format!(
+            "FixedTimespan {{ utc_offset: {}, dst_offset: {}, name: Some({:?}) }}",
+            timespan.utc_offset, timespan.dst_offset, timespan.name,
+        )
+    }
+
+    fn format_transition(transition: RuleTransition) -> String {
+        let date = match transition.date {
+            RuleDate::JulianDay(day) => format!("RuleDate::JulianDay({day})"),
+            RuleDate::DayOfYear(day) => format!("RuleDate::DayOfYear({day})"),
+            RuleDate::MonthWeekday {
+                month,
+                week,
+                weekday,
+            } => format!(
+                "RuleDate::MonthWeekday {{ month: {month}, week: {week}, weekday: {weekday} }}"
+            ),
+        };
+        format!(
+            "RuleTransition {{ date: {date}, time: {} }}",
+            transition.time
+        )
+    }
+
+    let (standard, daylight) = match extra {
+        Some(ExtraRule {
+            standard,
+            daylight: Some(daylight),
+        }) => (standard, daylight),
+        _ => return "None".to_string(),
+    };
+    format!(
+        "Some(&ExtraRule {{
+                standard: {standard},
+                daylight: Some(DaylightRule {{
+                    timespan: {timespan},
+                    start: {start},
+                    end: {end},
+                }}),
+            }})",
+        standard = format_timespan(&standard),
+        timespan = format_timespan(&daylight.timespan),
+        start = format_transition(daylight.start),
+        end = format_transition(daylight.end),
+    )
 }
 
 // Convert all '/' to '__', all '+' to 'Plus' and '-' to 'Minus', unless
@@ -77,12 +141,22 @@ fn convert_bad_chars(name: &str) -> String {
 // The timezone file contains impls of `Timespans` for all timezones in the
 // database. The `Wrap` wrapper in the `timezone_impl` module then implements
 // TimeZone for any contained struct that implements `Timespans`.
-fn write_timezone_file(timezone_file: &mut File, table: &Table, uncased: bool) -> io::Result<()> {
+fn write_timezone_file(
+    timezone_file: &mut File,
+    table: &Table,
+    tabs: &Tabs,
+    cldr: &Cldr,
+    uncased: bool,
+) -> io::Result<()> {
     let zones = table
         .zonesets
         .keys()
         .chain(table.links.keys())
         .collect::<BTreeSet<_>>();
+    let links = table.links.iter().collect::<BTreeMap<_, _>>();
+    let zone_tab = tabs.zone_entries(&zones);
+    #[cfg(feature = "filter-by-years")]
+    let years = years::Years::from_env();
     writeln!(
         timezone_file,
         "use core::fmt::{{self, Debug, Display, Formatter}};",
@@ -90,7 +164,18 @@ fn write_timezone_file(timezone_file: &mut File, table: &Table, uncased: bool) -
     writeln!(timezone_file, "use core::str::FromStr;\n",)?;
     writeln!(
         timezone_file,
-        "use crate::timezone_impl::{{TimeSpans, FixedTimespanSet, FixedTimespan}};\n",
+        "use crate::compact::CompactTimespans;
+use crate::timezone_impl::{{FixedTimespanSet, FixedTimespan}};",
+    )?;
+    // No zone may need an extra rule once the table is filtered, or the years cut short.
+    writeln!(
+        timezone_file,
+        "#[allow(unused_imports)]
+use crate::timezone_impl::{{ExtraRule, DaylightRule, RuleTransition, RuleDate}};",
+    )?;
+    writeln!(
+        timezone_file,
+        "use crate::country::{{Coordinates, Country}};\n",
     )?;
     writeln!(
         timezone_file,
@@ -122,6 +207,8 @@ fn write_timezone_file(timezone_file: &mut File, table: &Table, uncased: bool) -
         map.build()
     )?;
 
+    write_windows_zones(timezone_file, table, &zones, &cldr.windows_zones())?;
+
     #[cfg(feature = "case-insensitive")]
     if uncased {
         writeln!(timezone_file, "use uncased::UncasedStr;\n",)?;
@@ -178,6 +265,164 @@ impl FromStr for Tz {{
     }}"
     )?;
 
+    writeln!(
+        timezone_file,
+        "
+    /// The zone that this timezone is a link to, or itself if it isn't a link.
+    ///
+    /// Links are alternative names that the tz database keeps for zones, often for
+    /// backward compatibility, such as `Asia/Calcutta` for `Asia/Kolkata` or
+    /// `US/Eastern` for `America/New_York`. A link always has the same rules as
+    /// its zone.
+    pub fn canonical(self) -> Tz {{
+        match self {{"
+    )?;
+    for (link, target) in &links {
+        writeln!(
+            timezone_file,
+            "            Tz::{} => Tz::{},",
+            convert_bad_chars(link),
+            convert_bad_chars(target)
+        )?;
+    }
+    writeln!(
+        timezone_file,
+        "            _ => self,
+        }}
+    }}
+
+    /// Whether this timezone is a link to another zone; see [`Tz::canonical()`].
+    pub fn is_link(self) -> bool {{
+        self.canonical() != self
+    }}
+
+    /// The other names of the zone of this timezone: the zone itself if this is a
+    /// link, and all of its links, in alphabetical order.
+    pub fn aliases(self) -> &'static [Tz] {{
+        match self {{"
+    )?;
+    for zone in &zones {
+        let target = table.links.get(zone.as_str()).unwrap_or(zone);
+        let aliases = zones
+            .iter()
+            .filter(|&other| other != zone)
+            .filter(|&other| *other == target || table.links.get(other.as_str()) == Some(target))
+            .map(|other| format!("Tz::{}", convert_bad_chars(other)))
+            .collect::<Vec<_>>();
+        if !aliases.is_empty() {
+            writeln!(
+                timezone_file,
+                "            Tz::{} => &[{}],",
+                convert_bad_chars(zone),
+                aliases.join(", ")
+            )?;
+        }
+    }
+    writeln!(
+        timezone_file,
+        "            _ => &[],
+        }}
+    }}"
+    )?;
+
+    writeln!(
+        timezone_file,
+        "
+    /// The POSIX TZ string for the current rules of this timezone, such as
+    /// `EST5EDT,M3.2.0,M11.1.0` for `America/New_York`.
+    ///
+    /// This is the string that `zic` writes at the end of compiled TZif files, so it may
+    /// use the extensions of RFC 8536: quoted abbreviations like `<+03>`, and transition
+    /// times outside of 0 to 24 hours. Returns `None` if the current rules can't be
+    /// expressed as one.
+    pub fn posix_tz_string(self) -> Option<&'static str> {{
+        match self {{"
+    )?;
+    for zone in &zones {
+        let zone_name = convert_bad_chars(zone);
+        match table.extra_rule(zone) {
+            Some(extra) => writeln!(
+                timezone_file,
+                "            Tz::{zone_name} => Some({:?}),",
+                extra.to_string()
+            )?,
+            None => writeln!(timezone_file, "            Tz::{zone_name} => None,")?,
+        }
+    }
+    writeln!(
+        timezone_file,
+        "        }}
+    }}"
+    )?;
+
+    writeln!(
+        timezone_file,
+        "
+    /// The ISO 3166 codes of the countries where this timezone is used.
+    ///
+    /// This starts with the country that lists this name in `zone.tab`, followed by
+    /// the other countries that share its clocks according to `zone1970.tab`. Links
+    /// kept for backward compatibility, such as `US/Eastern`, have none.
+    pub fn countries(self) -> &'static [&'static str] {{
+        match self {{"
+    )?;
+    for (zone, entry) in &zone_tab {
+        writeln!(
+            timezone_file,
+            "            Tz::{} => &{:?},",
+            convert_bad_chars(zone),
+            entry.countries
+        )?;
+    }
+    writeln!(
+        timezone_file,
+        "            _ => &[],
+        }}
+    }}
+
+    /// The location of the principal city of this timezone, from `zone.tab`.
+    pub fn coordinates(self) -> Option<Coordinates> {{
+        match self {{"
+    )?;
+    for (zone, entry) in &zone_tab {
+        let degrees = |seconds: i32| seconds as f64 / 3600.0;
+        writeln!(
+            timezone_file,
+            "            Tz::{} => Some(Coordinates {{ latitude: {:?}, longitude: {:?} }}),",
+            convert_bad_chars(zone),
+            degrees(entry.coordinates.latitude),
+            degrees(entry.coordinates.longitude),
+        )?;
+    }
+    writeln!(
+        timezone_file,
+        "            _ => None,
+        }}
+    }}
+
+    /// The description of the area that this timezone covers within its country,
+    /// such as `most of Germany`, from `zone.tab`.
+    ///
+    /// Only countries with more than one timezone have these.
+    pub fn comment(self) -> Option<&'static str> {{
+        match self {{"
+    )?;
+    for (zone, entry) in &zone_tab {
+        if let Some(comment) = entry.comment {
+            writeln!(
+                timezone_file,
+                "            Tz::{} => Some({comment:?}),",
+                convert_bad_chars(zone),
+            )?;
+        }
+    }
+    writeln!(
+        timezone_file,
+        "            _ => None,
+        }}
+    }}"
+    )?;
+
     if uncased {
         writeln!(
             timezone_file,
@@ -210,55 +455,74 @@ impl FromStr for Tz {{
     )?;
     writeln!(
         timezone_file,
-        "impl TimeSpans for Tz {{
-    fn timespans(&self) -> FixedTimespanSet {{"
+        "impl Tz {{
+    /// The timespans of this zone, which are compiled in.
+    pub(crate) fn timespans(self) -> FixedTimespanSet<'static> {{"
     )?;
+    // Zones whose timespans are identical share the tables of the first of them,
+    // which is how links share the tables of their target too.
+    let mut distinct: HashMap<_, String> = HashMap::new();
+    let mut shared = BTreeMap::new();
     for zone in &zones {
-        if table.links.get(zone.as_str()).is_some() {
+        if table.links.contains_key(zone.as_str()) {
             continue;
         }
         let zone_name = convert_bad_chars(zone);
-        let timespans = table.timespans(zone).unwrap();
+        #[allow(unused_mut)]
+        let mut timespans = table.timespans(zone).unwrap();
+        #[allow(unused_mut)]
+        let mut extra = table.extra_rule(zone);
+        #[cfg(feature = "filter-by-years")]
+        years.truncate(&mut timespans, &mut extra);
+        let key = (timespans, extra);
+        if let Some(first) = distinct.get(&key) {
+            shared.insert(zone.as_str(), first.clone());
+            continue;
+        }
+        let (timespans, extra) = &key;
+        let extra = extra.clone();
+        // Statics rather than consts, so that identical zones share their tables.
+        let mut fields = String::new();
+        Compact::new(timespans).write_fields(&mut fields);
         writeln!(
             timezone_file,
-            "        const {zone}: FixedTimespanSet = FixedTimespanSet {{
-            first: FixedTimespan {{ utc_offset: {utc}, dst_offset: {dst}, name: Some({name:?}) }},
-            rest: {rest},
+            "        static {zone}: CompactTimespans = CompactTimespans {{
+{fields}            extra: {extra},
         }};\n",
             zone = zone_name.to_uppercase(),
-            rest = format_rest(timespans.rest),
-            utc = timespans.first.utc_offset,
-            dst = timespans.first.dst_offset,
-            name = timespans.first.name,
+            extra = format_extra(extra),
         )?;
+        distinct.insert(key, zone_name);
     }
 
     write!(
         timezone_file,
         "
-        match *self {{
+        let timespans = match self {{
 "
     )?;
 
     for zone in &zones {
         let zone_name = convert_bad_chars(zone);
-        let target_name = if let Some(target) = table.links.get(zone.as_str()) {
-            convert_bad_chars(target)
-        } else {
-            zone_name.clone()
+        let target = table.links.get(zone.as_str()).unwrap_or(zone);
+        let target_name = match shared.get(target.as_str()) {
+            Some(first) => first.clone(),
+            None => convert_bad_chars(target),
         };
         writeln!(
             timezone_file,
-            "            Tz::{zone_name} => {target_name},",
+            "            Tz::{zone_name} => &{target_name},",
             target_name = target_name.to_uppercase(),
         )?;
     }
     write!(
         timezone_file,
-        "        }}
+        "        }};
+        timespans.timespans()
     }}
 }}\n"
     )?;
+    write_golden_zones(timezone_file, table, &zones, cldr)?;
     write!(
         timezone_file,
         "/// An array of every known variant
@@ -280,10 +544,245 @@ pub static TZ_VARIANTS: [Tz; {num}] = [
             zone = convert_bad_chars(zone)
         )?;
     }
+    writeln!(timezone_file, "];\n")?;
+
+    let countries = tabs.countries();
+    writeln!(
+        timezone_file,
+        "/// Every country of `iso3166.tab`, sorted by code, with its timezones from `zone.tab`
+pub static COUNTRIES: [Country; {num}] = [",
+        num = countries.len()
+    )?;
+    for country in countries {
+        let country_zones = zone_tab
+            .iter()
+            .filter(|(_, entry)| entry.countries[0] == country.code)
+            .map(|(zone, _)| format!("Tz::{}", convert_bad_chars(zone)))
+            .collect::<Vec<_>>();
+        writeln!(
+            timezone_file,
+            "    Country {{ code: {:?}, name: {:?}, zones: &[{}] }},",
+            country.code,
+            country.name,
+            country_zones.join(", "),
+        )?;
+    }
     write!(timezone_file, "];")?;
     Ok(())
 }
 
+/// Reads a file of the chrono-tz crate, such as `tz/zone.tab`.
+fn read_data_file(path: &str) -> String {
+    let root = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap_or_else(|_| String::new()));
+    let path = root.join(path);
+    fs::read_to_string(&path).unwrap_or_else(|e| panic!("cannot open {}: {e}", path.display()))
+}
+
+/// The tables of countries and their zones that come with the tz database.
+struct Tabs {
+    iso3166: String,
+    zone: String,
+    zone1970: String,
+}
+
+impl Tabs {
+    fn load() -> Self {
+        Tabs {
+            iso3166: read_data_file("tz/iso3166.tab"),
+            zone: read_data_file("tz/zone.tab"),
+            zone1970: read_data_file("tz/zone1970.tab"),
+        }
+    }
+
+    /// The countries of `iso3166.tab`, sorted by code.
+    fn countries(&self) -> Vec<Country<'_>> {
+        let mut countries = self
+            .iso3166
+            .lines()
+            .filter_map(|line| Country::new(line).unwrap())
+            .collect::<Vec<_>>();
+        countries.sort_by_key(|country| country.code);
+        countries
+    }
+
+    /// The rows of `zone.tab` for `zones`, in the order of the file, each with the
+    /// countries that `zone1970.tab` adds for the same zone after its own.
+    fn zone_entries<'a>(&'a self, zones: &BTreeSet<&String>) -> Vec<(&'a str, ZoneTabEntry<'a>)> {
+        let zone1970 = self
+            .zone1970
+            .lines()
+            .filter_map(|line| ZoneTabEntry::new(line).unwrap())
+            .map(|entry| (entry.zone, entry.countries))
+            .collect::<BTreeMap<_, _>>();
+
+        let mut entries = Vec::new();
+        for line in self.zone.lines() {
+            let mut entry = match ZoneTabEntry::new(line).unwrap() {
+                Some(entry) if zones.contains(&entry.zone.to_owned()) => entry,
+                _ => continue,
+            };
+            for &country in zone1970.get(entry.zone).into_iter().flatten() {
+                if !entry.countries.contains(&country) {
+                    entry.countries.push(country);
+                }
+            }
+            entries.push((entry.zone, entry));
+        }
+        entries
+    }
+}
+
+/// The files of the Unicode CLDR that are vendored in `cldr/`.
+struct Cldr {
+    windows_zones: String,
+    timezone: String,
+    meta_zones: String,
+}
+
+impl Cldr {
+    fn load() -> Self {
+        Cldr {
+            windows_zones: read_data_file("cldr/windowsZones.xml"),
+            timezone: read_data_file("cldr/bcp47/timezone.json"),
+            meta_zones: read_data_file("cldr/supplemental/metaZones.json"),
+        }
+    }
+
+    fn windows_zones(&self) -> Vec<MapZone<'_>> {
+        cldr::windows_zones(&self.windows_zones)
+    }
+
+    /// The ID that CLDR uses for each zone and link of `table`, such as `Asia/Calcutta`
+    /// for `Asia/Kolkata`. Links that CLDR doesn't know have the ID of their target.
+    fn ids(&self, table: &Table) -> BTreeMap<String, String> {
+        let canonical = cldr::canonical_names(&self.timezone);
+        let mut ids = BTreeMap::new();
+        for name in table.zonesets.keys().chain(table.links.keys()) {
+            let target = table.links.get(name).unwrap_or(name);
+            let id = canonical
+                .get(name)
+                .or_else(|| canonical.get(target))
+                .unwrap_or(target);
+            ids.insert(name.clone(), id.clone());
+        }
+        ids
+    }
+
+    #[cfg(feature = "metazones")]
+    fn metazone_periods(&self) -> BTreeMap<String, Vec<cldr::MetazonePeriod>> {
+        cldr::metazone_periods(&self.meta_zones)
+    }
+
+    fn golden_zones(&self) -> BTreeSet<String> {
+        cldr::golden_zones(&self.meta_zones)
+    }
+}
+
+// Whether CLDR picks a zone, by its CLDR ID, to represent one of its metazones, such
+// as `America/New_York` for `America_Eastern`.
+fn write_golden_zones(
+    timezone_file: &mut File,
+    table: &Table,
+    zones: &BTreeSet<&String>,
+    cldr: &Cldr,
+) -> io::Result<()> {
+    let ids = cldr.ids(table);
+    let golden_zones = cldr.golden_zones();
+    let golden = zones
+        .iter()
+        .filter(|zone| golden_zones.contains(&ids[zone.as_str()]))
+        .map(|zone| format!("Tz::{}", convert_bad_chars(zone)))
+        .collect::<Vec<_>>();
+    // The timezone filter can leave no golden zones, which `matches!` can't take.
+    let is_golden_zone = match golden.is_empty() {
+        true => "false".to_owned(),
+        false => format!(
+            "matches!(\n            self,\n            {}\n        )",
+            golden.join("\n                | ")
+        ),
+    };
+    writeln!(
+        timezone_file,
+        "impl Tz {{
+    /// Whether CLDR picks this zone, or the zone that it links to, to represent one
+    /// of its metazones, such as `America/New_York` for `America_Eastern`.
+    #[cfg_attr(not(feature = \"std\"), allow(dead_code))] // only used to pick among zones at runtime
+    pub(crate) fn is_golden_zone(self) -> bool {{
+        {is_golden_zone}
+    }}
+}}\n"
+    )?;
+    Ok(())
+}
+
+// The mappings between Windows time zone IDs and zones, both ways. Windows IDs map
+// to the zone of each territory, with the default zone of territory `001` first.
+// Zones map to the ID of the first `mapZone` that lists them, or that lists the zone
+// that they link to, as CLDR often uses older names such as `Asia/Calcutta`.
+fn write_windows_zones(
+    timezone_file: &mut File,
+    table: &Table,
+    zones: &BTreeSet<&String>,
+    windows_zones: &[MapZone],
+) -> io::Result<()> {
+    let mut by_id = BTreeMap::<&str, Vec<(&str, &str)>>::new();
+    for map_zone in windows_zones {
+        let zone = match map_zone
+            .zones
+            .iter()
+            .find(|zone| zones.contains(&zone.to_string()))
+        {
+            Some(zone) => zone,
+            None => continue,
+        };
+        let territories = by_id.entry(map_zone.windows_id).or_default();
+        match map_zone.territory {
+            "001" => territories.insert(0, (map_zone.territory, zone)),
+            _ => territories.push((map_zone.territory, zone)),
+        }
+    }
+
+    let mut map = phf_codegen::Map::new();
+    for (windows_id, territories) in &by_id {
+        let territories = territories
+            .iter()
+            .map(|(territory, zone)| format!("({territory:?}, Tz::{})", convert_bad_chars(zone)))
+            .collect::<Vec<_>>();
+        map.entry(*windows_id, format!("&[{}]", territories.join(", ")));
+    }
+    writeln!(
+        timezone_file,
+        "pub(crate) static WINDOWS_ZONES: ::phf::Map<&'static str, &'static [(&'static str, Tz)]> = \n{};",
+        map.build()
+    )?;
+
+    let mut ids = BTreeMap::new();
+    for map_zone in windows_zones {
+        for zone in &map_zone.zones {
+            ids.entry(*zone).or_insert(map_zone.windows_id);
+        }
+    }
+    let mut map = phf_codegen::Map::new();
+    for zone in zones {
+        let target = table.links.get(zone.as_str()).unwrap_or(zone);
+        let aliases = zones
+            .iter()
+            .filter(|&alias| table.links.get(alias.as_str()) == Some(target));
+        let windows_id = [*zone, target]
+            .into_iter()
+            .chain(aliases.copied())
+            .find_map(|name| ids.get(name.as_str()));
+        if let Some(windows_id) = windows_id {
+            map.entry(zone.as_str(), format!("{windows_id:?}"));
+        }
+    }
+    writeln!(
+        timezone_file,
+        "pub(crate) static WINDOWS_IDS: ::phf::Map<&'static str, &'static str> = \n{};",
+        map.build()
+    )
+}
+
 // Create a file containing nice-looking re-exports such as Europe::London
 // instead of having to use chrono_tz::timezones::Europe__London
 fn write_directory_file(directory_file: &mut File, table: &Table, version: &str) -> io::Result<()> {
@@ -315,7 +814,7 @@ fn write_directory_file(directory_file: &mut File, table: &Table, version: &str)
 
         match first {
             true => first = false,
-            false => writeln!(directory_file, "")?,
+            false => writeln!(directory_file)?,
         }
 
         let module_name = convert_bad_chars(entry.name);
@@ -365,28 +864,49 @@ fn write_directory_file(directory_file: &mut File, table: &Table, version: &str)
     Ok(())
 }
 
-/// Module containing code supporting filter-by-regex feature
+/// Module containing code supporting the filter-by-regex and filter-by-list features
 ///
-/// The "GMT" and "UTC" time zones are always included.
-#[cfg(feature = "filter-by-regex")]
+/// The "GMT" and "UTC" time zones are always included. If both a regex and a list are
+/// given, the zones that either of them selects are kept.
+#[cfg(any(feature = "filter-by-regex", feature = "filter-by-list"))]
 mod filter {
+    #[cfg(feature = "filter-by-list")]
+    use std::collections::BTreeSet;
     use std::collections::HashSet;
     use std::env;
+    #[cfg(feature = "filter-by-list")]
+    use std::fs;
 
+    #[cfg(feature = "filter-by-regex")]
     use regex::Regex;
 
-    use crate::{Table, FILTER_ENV_VAR_NAME};
+    use crate::Table;
+    #[cfg(feature = "filter-by-regex")]
+    use crate::FILTER_ENV_VAR_NAME;
+    #[cfg(feature = "filter-by-list")]
+    use crate::ZONE_LIST_ENV_VAR_NAME;
 
-    /// Filter `table` by applying [`FILTER_ENV_VAR_NAME`].
+    /// Filter `table` by applying [`FILTER_ENV_VAR_NAME`] and [`ZONE_LIST_ENV_VAR_NAME`].
     pub(crate) fn maybe_filter_timezone_table(table: &mut Table) {
+        let mut filters = Vec::<Box<dyn Fn(&str) -> bool>>::new();
+        #[cfg(feature = "filter-by-regex")]
         if let Some(filter_regex) = get_filter_regex() {
-            filter_timezone_table(table, filter_regex);
+            filters.push(Box::new(move |name| filter_regex.is_match(name)));
+        }
+        #[cfg(feature = "filter-by-list")]
+        if let Some(zone_list) = get_zone_list(table) {
+            filters.push(Box::new(move |name| zone_list.contains(name)));
+        }
+
+        if !filters.is_empty() {
+            filter_timezone_table(table, |name| filters.iter().any(|filter| filter(name)));
         }
     }
 
     /// Checks the `CHRONO_TZ_TIMEZONE_FILTER` environment variable.
     /// Converts it to a regex if set. Panics if the regex is not valid, as we want
     /// to fail the build if that happens.
+    #[cfg(feature = "filter-by-regex")]
     fn get_filter_regex() -> Option<Regex> {
         match env::var(FILTER_ENV_VAR_NAME) {
             Ok(val) => {
@@ -408,6 +928,57 @@ mod filter {
         }
     }
 
+    /// Checks the `CHRONO_TZ_TIMEZONE_LIST` environment variable, and reads the zones
+    /// from the file that it names if set.
+    ///
+    /// The file has one zone or link name per line. Blank lines are ignored, as is
+    /// everything after a `#`. Panics if the file can't be read or names a zone that
+    /// is not in `table`, as we want to fail the build if that happens.
+    #[cfg(feature = "filter-by-list")]
+    fn get_zone_list(table: &Table) -> Option<HashSet<String>> {
+        let path = match env::var(ZONE_LIST_ENV_VAR_NAME) {
+            Ok(val) if val.trim().is_empty() => return None,
+            Ok(val) => val.trim().to_owned(),
+            Err(env::VarError::NotPresent) => return None,
+            Err(env::VarError::NotUnicode(s)) => panic!(
+                "The value '{s:?}' for environment variable {ZONE_LIST_ENV_VAR_NAME} is not valid Unicode"
+            ),
+        };
+        println!("cargo:rerun-if-changed={path}");
+        let contents = fs::read_to_string(&path).unwrap_or_else(|err| {
+            panic!(
+                "Cannot read the file '{path}' named by environment variable {ZONE_LIST_ENV_VAR_NAME}, err={err}"
+            )
+        });
+
+        let known = table.zonesets.keys().chain(table.links.keys());
+        let known = known.map(String::as_str).collect::<BTreeSet<_>>();
+        let mut zones = HashSet::new();
+        let mut unknown = Vec::new();
+        for (number, line) in contents.lines().enumerate() {
+            let name = line.split('#').next().unwrap().trim();
+            if name.is_empty() {
+                continue;
+            }
+            if known.contains(name) {
+                zones.insert(name.to_owned());
+                continue;
+            }
+            let mut message = format!("\n  {path}:{}: unknown time zone '{name}'", number + 1);
+            if let Some(similar) = known.iter().find(|zone| zone.eq_ignore_ascii_case(name)) {
+                message.push_str(&format!(", did you mean '{similar}'?"));
+            }
+            unknown.push(message);
+        }
+        if !unknown.is_empty() {
+            panic!(
+                "The file named by environment variable {ZONE_LIST_ENV_VAR_NAME} lists zones that are not in the IANA database:{}",
+                unknown.concat()
+            );
+        }
+        Some(zones)
+    }
+
     /// Insert a new name in the list of names to keep. If the name has 3
     /// parts, then also insert the 2-part prefix. If we don't do this we will lose
     /// half of Indiana in `directory.rs`. But we *don't* want to keep one-part names,
@@ -424,17 +995,18 @@ mod filter {
         keep.insert(new_value.to_string());
     }
 
-    /// Filter `table` by applying `filter_regex`.
-    fn filter_timezone_table(table: &mut Table, filter_regex: Regex) {
+    /// Filter `table` to the zones for which `is_match` holds, and the zones linked
+    /// to them.
+    fn filter_timezone_table(table: &mut Table, is_match: impl Fn(&str) -> bool) {
         // Compute the transitive closure of things to keep.
         // Doing this, instead of just filtering `zonesets` and `links` by the
         // regex, helps to keep the `structure()` intact.
         let mut keep = HashSet::new();
         for (k, v) in &table.links {
-            if filter_regex.is_match(k) || k == "GMT" || k == "UTC" {
+            if is_match(k) || k == "GMT" || k == "UTC" {
                 insert_keep_entry(&mut keep, k);
             }
-            if filter_regex.is_match(v) || k == "GMT" || k == "UTC" {
+            if is_match(v) || k == "GMT" || k == "UTC" {
                 insert_keep_entry(&mut keep, v);
             }
         }
@@ -470,7 +1042,7 @@ mod filter {
 
         table
             .zonesets
-            .retain(|k, _| filter_regex.is_match(k) || keep.iter().any(|s| k.starts_with(s)));
+            .retain(|k, _| is_match(k) || keep.iter().any(|s| k.starts_with(s)));
     }
 }
 
@@ -495,7 +1067,7 @@ fn detect_iana_db_version() -> String {
     unreachable!("no version found")
 }
 
-pub fn main(dir: &Path, _filter: bool, _uncased: bool) {
+fn load_table(_filter: bool) -> Table {
     let mut table = TableBuilder::new();
 
     let root = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap_or_else(|_| String::new()));
@@ -511,17 +1083,64 @@ pub fn main(dir: &Path, _filter: bool, _uncased: bool) {
 
     #[allow(unused_mut)]
     let mut table = table.build();
-    #[cfg(feature = "filter-by-regex")]
+    #[cfg(any(feature = "filter-by-regex", feature = "filter-by-list"))]
     if _filter {
         filter::maybe_filter_timezone_table(&mut table);
     }
+    table
+}
+
+pub fn main(dir: &Path, _filter: bool, _uncased: bool) {
+    let table = load_table(_filter);
 
     let timezone_path = dir.join("timezones.rs");
     let mut timezone_file = File::create(timezone_path).unwrap();
-    write_timezone_file(&mut timezone_file, &table, _uncased).unwrap();
+    let tabs = Tabs::load();
+    let cldr = Cldr::load();
+    write_timezone_file(&mut timezone_file, &table, &tabs, &cldr, _uncased).unwrap();
 
     let directory_path = dir.join("directory.rs");
     let mut directory_file = File::create(directory_path).unwrap();
     let version = detect_iana_db_version();
     write_directory_file(&mut directory_file, &table, &version).unwrap();
 }
+
+/// Writes a compiled TZif file for every zone and link into `dir`, laid out like
+/// `/usr/share/zoneinfo`, along with a `+VERSION` file.
+///
+/// The files hold the same data as the code written by [`main`], with a POSIX TZ
+/// string footer for the time after the last transition.
+pub fn write_tzif_files(dir: &Path, _filter: bool) {
+    let table = load_table(_filter);
+
+    let zones = table.zonesets.keys().chain(table.links.keys());
+    for zone in zones {
+        let path = dir.join(zone);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        let mut file = BufWriter::new(File::create(path).unwrap());
+        let timespans = table.timespans(zone).unwrap();
+        let extra = table.extra_rule(zone);
+        tzif::write_tzif(&mut file, &timespans, extra.as_ref()).unwrap();
+        file.flush().unwrap();
+    }
+
+    let version = detect_iana_db_version();
+    fs::write(dir.join("+VERSION"), format!("{version}\n")).unwrap();
+}
+
+/// Writes a tzdb bundle to `path`: a single file with the compiled TZif data of every
+/// zone, the links, the version of the database and, if `countries` is set, the
+/// country tables.
+///
+/// `chrono_tz::runtime::Database` loads these bundles, so that a program can pick up a
+/// new release of the database without being rebuilt or relying on a zoneinfo
+/// directory.
+pub fn write_bundle(path: &Path, _filter: bool, countries: bool) {
+    let table = load_table(_filter);
+    let tabs = countries.then(Tabs::load);
+    let version = detect_iana_db_version();
+
+    let mut file = BufWriter::new(File::create(path).unwrap());
+    bundle::write_bundle(&mut file, &table, &version, tabs.as_ref()).unwrap();
+    file.flush().unwrap();
+}
@@ -0,0 +1,90 @@
+//! Generating the metazones of zones over time from the `supplemental/metaZones.json`
+//! file of the Unicode CLDR that is vendored in `cldr/`.
+
+use std::collections::BTreeSet;
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::Path;
+
+use parse_zoneinfo::table::Table;
+
+use crate::{convert_bad_chars, Cldr};
+
+// The metazones of each zone over time, by the CLDR ID of the zone, in the same shape
+// as its timespans: the metazone before the first change, then the changes. Zones
+// are in no metazone outside of the periods that CLDR lists.
+fn write_metazones(file: &mut File, table: &Table) -> io::Result<()> {
+    let zones = table
+        .zonesets
+        .keys()
+        .chain(table.links.keys())
+        .collect::<BTreeSet<_>>();
+    let cldr = Cldr::load();
+    let ids = cldr.ids(table);
+    let periods = cldr.metazone_periods();
+    writeln!(
+        file,
+        "impl Metazones for Tz {{
+    fn metazones(&self) -> MetazoneSet {{
+        const NO_METAZONE: MetazoneSet = MetazoneSet {{ first: None, rest: &[] }};\n"
+    )?;
+    let ids_with_metazones = zones
+        .iter()
+        .map(|zone| &ids[zone.as_str()])
+        .filter(|id| periods.contains_key(id.as_str()))
+        .collect::<BTreeSet<_>>();
+    for id in &ids_with_metazones {
+        let mut first = None;
+        let mut rest = Vec::<(i64, Option<&str>)>::new();
+        for period in &periods[id.as_str()] {
+            let metazone = Some(period.metazone.as_str());
+            match period.from {
+                // A period that starts when the previous one ends replaces its end.
+                Some(from) => match rest.last_mut() {
+                    Some(last) if last.0 == from => last.1 = metazone,
+                    _ => rest.push((from, metazone)),
+                },
+                None => first = metazone,
+            }
+            if let Some(to) = period.to {
+                rest.push((to, None));
+            }
+        }
+
+        let mut changes = "&[\n".to_string();
+        for (start, metazone) in rest {
+            changes.push_str(&format!("                ({start}, {metazone:?}),\n"));
+        }
+        changes.push_str("            ]");
+        writeln!(
+            file,
+            "        const {name}: MetazoneSet = MetazoneSet {{
+            first: {first:?},
+            rest: {changes},
+        }};\n",
+            name = convert_bad_chars(id).to_uppercase(),
+        )?;
+    }
+
+    writeln!(file, "        match *self {{")?;
+    for zone in zones {
+        let id = &ids[zone.as_str()];
+        let set = match ids_with_metazones.contains(id) {
+            true => convert_bad_chars(id).to_uppercase(),
+            false => "NO_METAZONE".to_owned(),
+        };
+        writeln!(
+            file,
+            "            Tz::{} => {set},",
+            convert_bad_chars(zone)
+        )?;
+    }
+    writeln!(file, "        }}\n    }}\n}}")
+}
+
+/// Writes `metazones.rs` into `dir`, with the metazones of every zone over time.
+pub fn write_metazones_file(dir: &Path, _filter: bool) {
+    let table = crate::load_table(_filter);
+    let mut file = File::create(dir.join("metazones.rs")).unwrap();
+    write_metazones(&mut file, &table).unwrap();
+}
@@ -0,0 +1,280 @@
+//! Generating the localized names of zones from the `main/<locale>/timeZoneNames.json`
+//! files of the Unicode CLDR that are vendored in `cldr/`.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::Path;
+
+use parse_zoneinfo::table::Table;
+use parse_zoneinfo::transitions::TableTransitions;
+use serde_json::Value;
+
+use crate::{cldr, read_data_file, Cldr, LOCALES_ENV_VAR_NAME};
+
+/// Locales whose parent in CLDR isn't the locale that their tag truncates to, from
+/// `supplemental/parentLocales.json`. Only those that are vendored are listed.
+const PARENT_LOCALES: &[(&str, &str)] = &[("en-ZA", "en-001"), ("sr-Latn", "und")];
+
+/// The locale of the names that every other locale falls back to.
+const ROOT_LOCALE: &str = "und";
+
+/// The names of zones and metazones in one locale.
+struct LocaleNames {
+    locale: String,
+    parent: Option<String>,
+    exemplar_cities: BTreeMap<String, String>,
+    zones: BTreeMap<String, LongNames>,
+    metazones: BTreeMap<String, LongNames>,
+}
+
+/// The long names of a zone or metazone, as in `long` of `timeZoneNames.json`.
+struct LongNames {
+    generic: Option<String>,
+    standard: Option<String>,
+    daylight: Option<String>,
+}
+
+impl LongNames {
+    fn new(value: &Value) -> Option<Self> {
+        let long = value.get("long")?;
+        let name = |key: &str| long.get(key).and_then(Value::as_str).map(str::to_owned);
+        Some(LongNames {
+            generic: name("generic"),
+            standard: name("standard"),
+            daylight: name("daylight"),
+        })
+    }
+
+    fn to_code(&self) -> String {
+        format!(
+            "LongNames {{ generic: {:?}, standard: {:?}, daylight: {:?} }}",
+            self.generic, self.standard, self.daylight
+        )
+    }
+}
+
+/// The parent of `locale`: the one from [`PARENT_LOCALES`], or else the locale that
+/// its tag truncates to, or else the root locale.
+fn parent_locale(locale: &str) -> Option<String> {
+    if locale == ROOT_LOCALE {
+        return None;
+    }
+    if let Some(&(_, parent)) = PARENT_LOCALES.iter().find(|&&(child, _)| child == locale) {
+        return Some(parent.to_owned());
+    }
+    match locale.rsplit_once('-') {
+        Some((parent, _)) => Some(parent.to_owned()),
+        None => Some(ROOT_LOCALE.to_owned()),
+    }
+}
+
+/// The locales that are vendored in `cldr/main`, sorted.
+fn vendored_locales() -> BTreeSet<String> {
+    let root = env::var("CARGO_MANIFEST_DIR").unwrap_or_else(|_| String::new());
+    let dir = Path::new(&root).join("cldr/main");
+    fs::read_dir(&dir)
+        .unwrap_or_else(|e| panic!("cannot open {}: {e}", dir.display()))
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect()
+}
+
+/// The locales to write names for: those listed in [`LOCALES_ENV_VAR_NAME`], or all
+/// vendored locales if it is unset or empty, along with their parents. Panics if a
+/// listed locale is not vendored, as we want to fail the build if that happens.
+fn selected_locales() -> BTreeSet<String> {
+    let vendored = vendored_locales();
+    let requested = match env::var(LOCALES_ENV_VAR_NAME) {
+        Ok(val) if !val.trim().is_empty() => val,
+        Ok(_) | Err(env::VarError::NotPresent) => return vendored,
+        Err(env::VarError::NotUnicode(s)) => panic!(
+            "The value '{s:?}' for environment variable {LOCALES_ENV_VAR_NAME} is not valid Unicode"
+        ),
+    };
+
+    let mut locales = BTreeSet::new();
+    for locale in requested
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+    {
+        let locale = locale.replace('_', "-");
+        if !vendored.contains(&locale) {
+            panic!(
+                "The locale '{locale}' in environment variable {LOCALES_ENV_VAR_NAME} is not vendored in cldr/main"
+            );
+        }
+        let mut locale = Some(locale);
+        while let Some(current) = locale {
+            locale = parent_locale(&current);
+            if vendored.contains(&current) {
+                locales.insert(current);
+            }
+        }
+    }
+    locales.insert(ROOT_LOCALE.to_owned());
+    locales
+}
+
+/// Reads the names of `locale`, for the CLDR zone IDs in `ids`.
+fn read_locale(locale: &str, ids: &BTreeSet<&str>, vendored: &BTreeSet<String>) -> LocaleNames {
+    let json = read_data_file(&format!("cldr/main/{locale}/timeZoneNames.json"));
+    let json = serde_json::from_str::<Value>(&json)
+        .unwrap_or_else(|e| panic!("invalid timeZoneNames.json for {locale}: {e}"));
+    let names = &json["main"][locale]["dates"]["timeZoneNames"];
+
+    let mut exemplar_cities = BTreeMap::new();
+    let mut zones = BTreeMap::new();
+    for (id, value) in cldr::leaves(&names["zone"]) {
+        if !ids.contains(id.as_str()) {
+            continue;
+        }
+        if let Some(city) = value.get("exemplarCity").and_then(Value::as_str) {
+            exemplar_cities.insert(id.clone(), city.to_owned());
+        }
+        if let Some(long) = LongNames::new(value) {
+            zones.insert(id, long);
+        }
+    }
+
+    // The root locale has no exemplar city for most zones, as CLDR derives them
+    // from the last part of the ID. That is not done for `Etc/` zones or IDs such
+    // as `EST5EDT`, which name no city.
+    if locale == ROOT_LOCALE {
+        for &id in ids {
+            match id.rsplit_once('/') {
+                Some((_, city)) if !id.starts_with("Etc/") => {
+                    exemplar_cities
+                        .entry(id.to_owned())
+                        .or_insert_with(|| city.replace('_', " "));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let mut metazones = BTreeMap::new();
+    for (metazone, value) in names["metazone"].as_object().into_iter().flatten() {
+        if let Some(long) = LongNames::new(value) {
+            metazones.insert(metazone.clone(), long);
+        }
+    }
+
+    let mut parent = parent_locale(locale);
+    while let Some(locale) = parent.as_deref().filter(|p| !vendored.contains(*p)) {
+        parent = parent_locale(locale);
+    }
+    LocaleNames {
+        locale: locale.to_owned(),
+        parent,
+        exemplar_cities,
+        zones,
+        metazones,
+    }
+}
+
+fn write_map<'a>(
+    file: &mut File,
+    entries: impl Iterator<Item = (&'a String, String)>,
+) -> io::Result<()> {
+    let mut map = phf_codegen::Map::new();
+    for (key, value) in entries {
+        map.entry(key.as_str(), value);
+    }
+    write!(file, "{}", map.build())
+}
+
+// The localized names of the zones of `table`, keyed by CLDR zone ID. Zones that CLDR
+// knows by another name, such as `Asia/Kolkata` for `Asia/Calcutta`, map to that ID.
+fn write_names(file: &mut File, table: &Table, locales: &BTreeSet<String>) -> io::Result<()> {
+    let ids = Cldr::load().ids(table);
+    writeln!(
+        file,
+        "static CLDR_IDS: ::phf::Map<&'static str, &'static str> = "
+    )?;
+    write_map(
+        file,
+        ids.iter()
+            .filter(|(name, id)| name != id)
+            .map(|(name, id)| (name, format!("{id:?}"))),
+    )?;
+    writeln!(file, ";\n")?;
+    let ids_of_zones = ids.values().map(String::as_str).collect::<BTreeSet<_>>();
+
+    // Zones such as `Europe/Dublin` observe a negative DST offset in winter, so the
+    // daylight time of CLDR is when their DST offset is zero. These map to the total
+    // offset of their winter time, which CLDR calls standard time.
+    let mut negative_dst = BTreeMap::new();
+    for name in table.zonesets.keys() {
+        let daylight = table.extra_rule(name).and_then(|rule| rule.daylight);
+        if let Some(daylight) = daylight.filter(|daylight| daylight.timespan.dst_offset < 0) {
+            let timespan = daylight.timespan;
+            negative_dst.insert(&ids[name], timespan.utc_offset + timespan.dst_offset);
+        }
+    }
+    writeln!(
+        file,
+        "static NEGATIVE_DST: ::phf::Map<&'static str, i32> = "
+    )?;
+    write_map(
+        file,
+        negative_dst
+            .into_iter()
+            .map(|(id, offset)| (id, offset.to_string())),
+    )?;
+    writeln!(file, ";\n")?;
+
+    let vendored = vendored_locales();
+    let locales = locales
+        .iter()
+        .map(|locale| read_locale(locale, &ids_of_zones, &vendored))
+        .collect::<Vec<_>>();
+    let index = locales
+        .iter()
+        .enumerate()
+        .map(|(i, names)| (names.locale.as_str(), i))
+        .collect::<HashMap<_, _>>();
+
+    writeln!(file, "static LOCALES: [LocaleNames; {}] = [", locales.len())?;
+    for names in &locales {
+        let parent = names.parent.as_deref().map(|parent| index[parent]);
+        writeln!(
+            file,
+            "    LocaleNames {{\n        locale: {:?},\n        parent: {parent:?},",
+            names.locale
+        )?;
+        write!(file, "        exemplar_cities: ")?;
+        write_map(
+            file,
+            names
+                .exemplar_cities
+                .iter()
+                .map(|(id, city)| (id, format!("{city:?}"))),
+        )?;
+        write!(file, ",\n        zones: ")?;
+        write_map(
+            file,
+            names.zones.iter().map(|(id, long)| (id, long.to_code())),
+        )?;
+        write!(file, ",\n        metazones: ")?;
+        write_map(
+            file,
+            names
+                .metazones
+                .iter()
+                .map(|(id, long)| (id, long.to_code())),
+        )?;
+        writeln!(file, ",\n    }},")?;
+    }
+    writeln!(file, "];")?;
+    Ok(())
+}
+
+/// Writes `names.rs` into `dir`, with the localized names of zones in the locales
+/// selected by [`LOCALES_ENV_VAR_NAME`].
+pub fn write_names_file(dir: &Path, _filter: bool) {
+    let table = crate::load_table(_filter);
+    let mut file = File::create(dir.join("names.rs")).unwrap();
+    write_names(&mut file, &table, &selected_locales()).unwrap();
+}
@@ -0,0 +1,134 @@
+//! Writing compiled TZif files, as described in RFC 8536.
+//!
+//! This is what `zic` does with the tz database, but it starts from the same
+//! `parse_zoneinfo` data as the generated Rust code, so both always agree.
+
+use std::io::{self, Write};
+
+use parse_zoneinfo::transitions::{ExtraRule, FixedTimespan, FixedTimespanSet};
+
+/// Writes the TZif file of a zone with the given timespans, and `extra` as the
+/// POSIX TZ string footer that applies after the last transition.
+///
+/// The file is version 2, unless the footer needs the transition times of version 3.
+pub(crate) fn write_tzif(
+    out: &mut impl Write,
+    timespans: &FixedTimespanSet,
+    extra: Option<&ExtraRule>,
+) -> io::Result<()> {
+    let version = match extra.and_then(|extra| extra.daylight.as_ref()) {
+        Some(daylight)
+            if [daylight.start, daylight.end]
+                .iter()
+                .any(|transition| !(0..24 * 3600).contains(&transition.time)) =>
+        {
+            b'3'
+        }
+        _ => b'2',
+    };
+
+    // Readers that only know version 1 get the transitions that fit in 32 bits.
+    let block = Block::new(timespans, |at| i32::try_from(at).is_ok());
+    block.write(out, version, 4)?;
+    let block = Block::new(timespans, |_| true);
+    block.write(out, version, 8)?;
+
+    match extra {
+        Some(extra) => writeln!(out, "\n{extra}"),
+        None => writeln!(out, "\n"),
+    }
+}
+
+/// A header and data block of a TZif file.
+#[derive(Default)]
+struct Block<'a> {
+    /// The transition times, each with the index of the local time type it switches to.
+    transitions: Vec<(i64, u8)>,
+    /// The local time types, where the first one is in effect before any transition,
+    /// each with the index of its abbreviation in `names`.
+    types: Vec<(&'a FixedTimespan, u8)>,
+    /// The abbreviations of the types, each followed by a NUL byte.
+    names: Vec<u8>,
+}
+
+impl<'a> Block<'a> {
+    fn new(timespans: &'a FixedTimespanSet, in_range: impl Fn(i64) -> bool) -> Self {
+        // When earlier transitions don't fit, start with the timespan they lead to.
+        let mut first = &timespans.first;
+        let mut rest = Vec::new();
+        for (at, timespan) in &timespans.rest {
+            match in_range(*at) {
+                true => rest.push((*at, timespan)),
+                false if rest.is_empty() => first = timespan,
+                false => {}
+            }
+        }
+
+        let mut block = Block::default();
+        block.type_index(first);
+        for (at, timespan) in rest {
+            let index = block.type_index(timespan);
+            block.transitions.push((at, index));
+        }
+        block
+    }
+
+    fn type_index(&mut self, timespan: &'a FixedTimespan) -> u8 {
+        let same = |&(other, _): &(&FixedTimespan, u8)| {
+            other.total_offset() == timespan.total_offset()
+                && (other.dst_offset != 0) == (timespan.dst_offset != 0)
+                && other.name == timespan.name
+        };
+        if let Some(index) = self.types.iter().position(same) {
+            return index as u8;
+        }
+
+        let name_index = match self
+            .types
+            .iter()
+            .find(|(other, _)| other.name == timespan.name)
+        {
+            Some(&(_, name_index)) => name_index,
+            None => {
+                let name_index = self.names.len();
+                self.names.extend_from_slice(timespan.name.as_bytes());
+                self.names.push(0);
+                u8::try_from(name_index).expect("abbreviations too long")
+            }
+        };
+        self.types.push((timespan, name_index));
+        u8::try_from(self.types.len() - 1).expect("too many local time types")
+    }
+
+    fn write(&self, out: &mut impl Write, version: u8, time_size: usize) -> io::Result<()> {
+        out.write_all(b"TZif")?;
+        out.write_all(&[version])?;
+        out.write_all(&[0; 15])?;
+        // No UT/local or standard/wall indicators, and no leap seconds.
+        for count in [
+            0,
+            0,
+            0,
+            self.transitions.len(),
+            self.types.len(),
+            self.names.len(),
+        ] {
+            out.write_all(&(count as u32).to_be_bytes())?;
+        }
+
+        for &(at, _) in &self.transitions {
+            match time_size {
+                4 => out.write_all(&(at as i32).to_be_bytes())?,
+                _ => out.write_all(&at.to_be_bytes())?,
+            }
+        }
+        for &(_, index) in &self.transitions {
+            out.write_all(&[index])?;
+        }
+        for &(timespan, name_index) in &self.types {
+            out.write_all(&(timespan.total_offset() as i32).to_be_bytes())?;
+            out.write_all(&[(timespan.dst_offset != 0) as u8, name_index])?;
+        }
+        out.write_all(&self.names)
+    }
+}
@@ -0,0 +1,113 @@
+//! Limiting the generated transitions to a range of years.
+//!
+//! Outside of the range, the zones keep the timespan in effect at its nearest end:
+//! transitions before the range are folded into the first timespan, and the ones
+//! after it are dropped along with the rule that follows them.
+
+use std::env;
+
+use parse_zoneinfo::transitions::{ExtraRule, FixedTimespanSet};
+
+use crate::YEARS_ENV_VAR_NAME;
+
+/// `parse_zoneinfo` writes out the transitions of yearly rules until 2100, after
+/// which only the extra rule knows about them.
+const LAST_YEAR: i64 = 2100;
+
+/// A range of years, as the Unix timestamps at which they start. Both ends are
+/// optional, and `end` is exclusive.
+#[derive(Debug, Default)]
+pub(crate) struct Years {
+    start: Option<i64>,
+    end: Option<i64>,
+}
+
+impl Years {
+    /// Checks the `CHRONO_TZ_YEARS` environment variable. Panics if it is not a valid
+    /// range, as we want to fail the build if that happens.
+    pub(crate) fn from_env() -> Self {
+        match env::var(YEARS_ENV_VAR_NAME) {
+            Ok(val) => Years::parse(&val).unwrap_or_else(|err| {
+                panic!(
+                    "The value '{val:?}' for environment variable {YEARS_ENV_VAR_NAME} is not a valid range of years, err={err}"
+                )
+            }),
+            Err(env::VarError::NotPresent) => Years::default(),
+            Err(env::VarError::NotUnicode(s)) => panic!(
+                "The value '{s:?}' for environment variable {YEARS_ENV_VAR_NAME} is not valid Unicode"
+            ),
+        }
+    }
+
+    /// Parses a range such as `2000..2040`, `2000..=2039`, `2000..` or `..2040`.
+    fn parse(val: &str) -> Result<Self, String> {
+        let val = val.trim();
+        if val.is_empty() {
+            return Ok(Years::default());
+        }
+        let (start, end) = val
+            .split_once("..")
+            .ok_or("expected a range such as 2000..2040")?;
+        let (end, inclusive) = match end.strip_prefix('=') {
+            Some(end) => (end, true),
+            None => (end, false),
+        };
+
+        let year = |year: &str| match year.trim() {
+            "" => Ok(None),
+            year => year
+                .parse::<i64>()
+                .map(Some)
+                .map_err(|_| format!("'{year}' is not a year")),
+        };
+        let start = year(start)?;
+        let end = match year(end)? {
+            Some(end) if inclusive => Some(end + 1),
+            Some(end) => Some(end),
+            None if inclusive => return Err("an inclusive range needs an end".to_owned()),
+            None => None,
+        };
+
+        if let (Some(start), Some(end)) = (start, end) {
+            if start >= end {
+                return Err("the range is empty".to_owned());
+            }
+        }
+        if let Some(end) = end.filter(|&end| end > LAST_YEAR) {
+            return Err(format!(
+                "the range can end in {LAST_YEAR} at the latest, not {end}; leave the end out to keep the current rules"
+            ));
+        }
+        Ok(Years {
+            start: start.map(year_start),
+            end: end.map(year_start),
+        })
+    }
+
+    /// Drops the transitions of `timespans` outside of the range, along with `extra`
+    /// if the range has an end.
+    pub(crate) fn truncate(&self, timespans: &mut FixedTimespanSet, extra: &mut Option<ExtraRule>) {
+        if let Some(start) = self.start {
+            let before = timespans
+                .rest
+                .iter()
+                .take_while(|(at, _)| *at <= start)
+                .count();
+            if let Some((_, first)) = timespans.rest.drain(..before).next_back() {
+                timespans.first = first;
+            }
+        }
+        if let Some(end) = self.end {
+            timespans.rest.retain(|(at, _)| *at < end);
+            *extra = None;
+        }
+    }
+}
+
+/// The Unix timestamp of January 1st of `year`, at midnight UTC.
+fn year_start(year: i64) -> i64 {
+    // The leap days in the years up to `year`, less the ones before 1970.
+    let leap_days = |year: i64| year.div_euclid(4) - year.div_euclid(100) + year.div_euclid(400);
+    let days = 365 * (year - 1970) + leap_days(year - 1) - leap_days(1969);
+    days * 86400
+}
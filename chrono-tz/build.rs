@@ -1,16 +1,51 @@
-#[cfg(any(feature = "filter-by-regex", feature = "case-insensitive"))]
+#[cfg(any(
+    feature = "filter-by-regex",
+    feature = "filter-by-list",
+    feature = "filter-by-years",
+    feature = "case-insensitive",
+    feature = "metazones"
+))]
 use std::{env, path::Path};
 
 #[cfg(feature = "filter-by-regex")]
 use chrono_tz_build::FILTER_ENV_VAR_NAME;
+#[cfg(feature = "localized-names")]
+use chrono_tz_build::LOCALES_ENV_VAR_NAME;
+#[cfg(feature = "filter-by-years")]
+use chrono_tz_build::YEARS_ENV_VAR_NAME;
+#[cfg(feature = "filter-by-list")]
+use chrono_tz_build::ZONE_LIST_ENV_VAR_NAME;
 
 fn main() {
     #[cfg(feature = "filter-by-regex")]
     println!("cargo:rerun-if-env-changed={FILTER_ENV_VAR_NAME}");
-    #[cfg(any(feature = "filter-by-regex", feature = "case-insensitive"))]
+    #[cfg(feature = "filter-by-list")]
+    println!("cargo:rerun-if-env-changed={ZONE_LIST_ENV_VAR_NAME}");
+    #[cfg(feature = "filter-by-years")]
+    println!("cargo:rerun-if-env-changed={YEARS_ENV_VAR_NAME}");
+    #[cfg(any(
+        feature = "filter-by-regex",
+        feature = "filter-by-list",
+        feature = "filter-by-years",
+        feature = "case-insensitive"
+    ))]
     chrono_tz_build::main(
         Path::new(&env::var("OUT_DIR").unwrap()),
-        cfg!(feature = "filter-by-regex"),
+        cfg!(any(feature = "filter-by-regex", feature = "filter-by-list")),
         cfg!(feature = "case-insensitive"),
     );
+
+    #[cfg(feature = "metazones")]
+    chrono_tz_build::write_metazones_file(
+        Path::new(&env::var("OUT_DIR").unwrap()),
+        cfg!(any(feature = "filter-by-regex", feature = "filter-by-list")),
+    );
+
+    #[cfg(feature = "localized-names")]
+    println!("cargo:rerun-if-env-changed={LOCALES_ENV_VAR_NAME}");
+    #[cfg(feature = "localized-names")]
+    chrono_tz_build::write_names_file(
+        Path::new(&env::var("OUT_DIR").unwrap()),
+        cfg!(any(feature = "filter-by-regex", feature = "filter-by-list")),
+    );
 }
@@ -0,0 +1,93 @@
+//! The compact encoding of the timespans compiled into [`Tz`](crate::Tz)
+//!
+//! Like a TZif file, a zone keeps each of its distinct timespans once, and refers to
+//! them with an index byte per transition. The transition times are the differences
+//! from the previous transition, as variable-length integers that count hours when
+//! the difference is a whole number of them, which it nearly always is. The time of
+//! every [`BLOCK`]th transition is kept in full, along with where the differences
+//! that follow it start, so that finding a transition only decodes its block.
+//!
+//! `chrono-tz-build` writes this encoding, and the two have to agree.
+
+use crate::timezone_impl::{ExtraRule, FixedTimespan, FixedTimespanSet, TransitionTable};
+
+/// The number of transitions in a block.
+const BLOCK: usize = 16;
+
+/// The timespans of a zone in the compact encoding.
+pub struct CompactTimespans {
+    /// The distinct timespans of the zone, starting with the one in effect before
+    /// the first transition.
+    pub types: &'static [FixedTimespan<'static>],
+    /// The index in `types` of the timespan that each transition starts.
+    pub indices: &'static [u8],
+    /// The differences between the times of the transitions that are not the first
+    /// of their block.
+    pub deltas: &'static [u8],
+    /// The time of the first transition of each block, with the index in `deltas` at
+    /// which the rest of the block starts.
+    pub blocks: &'static [(i64, u16)],
+    /// The rule that keeps producing timespans after the last transition.
+    pub extra: Option<&'static ExtraRule<'static>>,
+}
+
+impl CompactTimespans {
+    pub(crate) fn timespans(&'static self) -> FixedTimespanSet<'static> {
+        FixedTimespanSet {
+            first: self.types[0],
+            rest: TransitionTable::Compact(self),
+            extra: self.extra.copied(),
+        }
+    }
+
+    /// The number of transitions.
+    pub(crate) fn len(&self) -> usize {
+        self.indices.len()
+    }
+
+    /// The transition at `index`, with the timespan that it starts.
+    pub(crate) fn get(&self, index: usize) -> (i64, FixedTimespan<'static>) {
+        let (mut time, start) = self.blocks[index / BLOCK];
+        let mut deltas = &self.deltas[start as usize..];
+        for _ in 0..index % BLOCK {
+            time += read_delta(&mut deltas);
+        }
+        (time, self.timespan(index))
+    }
+
+    /// The timespan that the transition at `index` starts.
+    pub(crate) fn timespan(&self, index: usize) -> FixedTimespan<'static> {
+        self.types[self.indices[index] as usize]
+    }
+
+    /// The number of bytes that the tables of this zone take up.
+    #[cfg(test)]
+    pub(crate) fn size(&self) -> usize {
+        use core::mem::size_of_val;
+
+        size_of_val(self.types)
+            + size_of_val(self.indices)
+            + size_of_val(self.deltas)
+            + size_of_val(self.blocks)
+    }
+}
+
+/// Reads a little-endian base 128 number, whose lowest bit says whether the rest
+/// counts seconds or hours.
+fn read_delta(bytes: &mut &[u8]) -> i64 {
+    let mut value = 0;
+    let mut shift = 0;
+    loop {
+        let byte = bytes[0];
+        *bytes = &bytes[1..];
+        value |= ((byte & 0x7f) as i64) << shift;
+        if byte & 0x80 == 0 {
+            break;
+        }
+        shift += 7;
+    }
+    match value & 1 {
+        0 => (value >> 1) * 3600,
+        _ => value >> 1,
+    }
+}
@@ -0,0 +1,53 @@
+//! Countries and the timezones used in them, from the `iso3166.tab`, `zone.tab` and
+//! `zone1970.tab` tables of the tz database.
+
+use crate::timezones::{Tz, COUNTRIES};
+
+/// A country, with the timezones that the tz database lists for it.
+///
+/// See [`COUNTRIES`](crate::COUNTRIES) for all of them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Country {
+    /// The ISO 3166 alpha-2 code of the country, such as `DE`.
+    pub code: &'static str,
+    /// The name of the country in English, such as `Germany`.
+    pub name: &'static str,
+    /// The timezones of the country, with the most populous areas first.
+    pub zones: &'static [Tz],
+}
+
+/// The location of the principal city of a timezone, in degrees.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Coordinates {
+    /// The latitude, where positive values are north of the equator.
+    pub latitude: f64,
+    /// The longitude, where positive values are east of Greenwich.
+    pub longitude: f64,
+}
+
+/// Looks up a country by its ISO 3166 alpha-2 code, such as `DE`.
+pub fn country(code: &str) -> Option<&'static Country> {
+    COUNTRIES
+        .binary_search_by(|country| country.code.cmp(code))
+        .ok()
+        .map(|index| &COUNTRIES[index])
+}
+
+/// The timezones of the country with the given ISO 3166 alpha-2 code, with the
+/// most populous areas first.
+///
+/// These are the names that `zone.tab` gives for the country, so Iceland has
+/// `Atlantic/Reykjavik` even though it shares its clocks with `Africa/Abidjan`.
+/// Use [`Tz::comment()`] to tell apart the zones of a country. Returns an empty
+/// slice for unknown codes.
+///
+/// ```
+/// use chrono_tz::{zones_for_country, Tz};
+///
+/// let zones = zones_for_country("DE");
+/// assert_eq!(zones, [Tz::Europe__Berlin, Tz::Europe__Busingen]);
+/// assert_eq!(zones[0].comment(), Some("most of Germany"));
+/// ```
+pub fn zones_for_country(code: &str) -> &'static [Tz] {
+    country(code).map_or(&[], |country| country.zones)
+}
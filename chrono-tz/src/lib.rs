@@ -132,13 +132,38 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
 #[cfg(feature = "serde")]
-mod serde;
+pub mod serde;
 
 mod binary_search;
-#[cfg(not(any(feature = "case-insensitive", feature = "filter-by-regex")))]
+mod compact;
+mod country;
+#[cfg(feature = "metazones")]
+mod metazone;
+#[cfg(feature = "localized-names")]
+mod names;
+#[cfg(feature = "std")]
+mod owned;
+#[cfg(feature = "std")]
+mod posix;
+#[cfg(not(any(
+    feature = "case-insensitive",
+    feature = "filter-by-regex",
+    feature = "filter-by-list",
+    feature = "filter-by-years"
+)))]
 use prebuilt::directory;
 mod prebuilt;
-#[cfg(any(feature = "case-insensitive", feature = "filter-by-regex"))]
+mod rfc9557;
+#[cfg(feature = "std")]
+pub mod runtime;
+#[cfg(feature = "std")]
+mod system;
+#[cfg(any(
+    feature = "case-insensitive",
+    feature = "filter-by-regex",
+    feature = "filter-by-list",
+    feature = "filter-by-years"
+))]
 mod directory {
     #![allow(
         dead_code,
@@ -149,44 +174,80 @@ mod directory {
     include!(concat!(env!("OUT_DIR"), "/directory.rs"));
 }
 mod timezone_impl;
-#[cfg(not(any(feature = "case-insensitive", feature = "filter-by-regex")))]
+#[cfg(feature = "std")]
+mod tzif;
+#[cfg(feature = "std")]
+mod vtimezone;
+mod windows;
+#[cfg(not(any(
+    feature = "case-insensitive",
+    feature = "filter-by-regex",
+    feature = "filter-by-list",
+    feature = "filter-by-years"
+)))]
 use prebuilt::timezones;
-#[cfg(any(feature = "case-insensitive", feature = "filter-by-regex"))]
+#[cfg(any(
+    feature = "case-insensitive",
+    feature = "filter-by-regex",
+    feature = "filter-by-list",
+    feature = "filter-by-years"
+))]
 mod timezones {
     #![allow(non_camel_case_types, clippy::unreadable_literal)]
     include!(concat!(env!("OUT_DIR"), "/timezones.rs"));
 }
 
-pub use crate::timezone_impl::{GapInfo, OffsetComponents, OffsetName, TzOffset};
+pub use crate::country::{country, zones_for_country, Coordinates, Country};
+#[cfg(feature = "std")]
+pub use crate::posix::{PosixTz, PosixTzError, PosixTzOffset};
+pub use crate::rfc9557::{format_rfc9557, parse_rfc9557, OffsetConflict, Rfc9557Error};
+#[cfg(feature = "std")]
+pub use crate::system::{system_tz, system_tz_with, SystemTzConfig, SystemTzError};
+pub use crate::timezone_impl::{
+    Disambiguation, Equivalence, FoldInfo, GapInfo, OffsetComponents, OffsetName, ResolveError,
+    Transition, Transitions, TzOffset,
+};
+#[cfg(feature = "std")]
+pub use crate::tzif::{DynTz, DynTzOffset, TzifError};
+#[cfg(feature = "std")]
+pub use crate::vtimezone::VTimezoneError;
 pub use directory::*;
 pub use timezones::ParseError;
 pub use timezones::Tz;
+pub use timezones::COUNTRIES;
 pub use timezones::TZ_VARIANTS;
 pub use IANA_TZDB_VERSION;
 
 #[cfg(test)]
 mod tests {
+    use super::country;
+    use super::zones_for_country;
     use super::Africa::Addis_Ababa;
     use super::America::Danmarkshavn;
     use super::America::Scoresbysund;
     use super::Antarctica::Casey;
     use super::Asia::Dhaka;
     use super::Australia::Adelaide;
+    use super::Coordinates;
+    use super::Disambiguation;
     use super::Europe::Berlin;
     use super::Europe::London;
     use super::Europe::Moscow;
     use super::Europe::Vilnius;
     use super::Europe::Warsaw;
+    use super::FoldInfo;
     use super::GapInfo;
     use super::Pacific::Apia;
     use super::Pacific::Noumea;
     use super::Pacific::Tahiti;
     use super::Tz;
+    use super::COUNTRIES;
     use super::IANA_TZDB_VERSION;
+    use super::TZ_VARIANTS;
     use super::US::Eastern;
     use super::UTC;
     use chrono::NaiveDateTime;
-    use chrono::{Duration, NaiveDate, TimeZone};
+    use chrono::{DateTime, Datelike, Duration, NaiveDate, TimeZone, Utc};
 
     #[test]
     fn london_to_berlin() {
@@ -624,6 +685,512 @@ mod tests {
         );
     }
 
+    #[test]
+    fn gap_info_after_2099() {
+        gap_info_test(
+            Tz::America__New_York,
+            NaiveDate::from_ymd_opt(2150, 3, 8)
+                .unwrap()
+                .and_hms_opt(2, 0, 0)
+                .unwrap(),
+            NaiveDate::from_ymd_opt(2150, 3, 8)
+                .unwrap()
+                .and_hms_opt(3, 0, 0)
+                .unwrap(),
+        );
+        let repeated = NaiveDate::from_ymd_opt(2150, 11, 1)
+            .unwrap()
+            .and_hms_opt(1, 30, 0)
+            .unwrap();
+        assert!(GapInfo::new(&repeated, &Tz::America__New_York).is_none());
+    }
+
+    fn fold_info_test(tz: Tz, fold_begin: NaiveDateTime, fold_end: NaiveDateTime) {
+        let in_fold = fold_begin + (fold_end - fold_begin) / 2;
+        let FoldInfo {
+            begin,
+            end,
+            earlier,
+            later,
+        } = FoldInfo::new(&in_fold, &tz).unwrap();
+
+        assert_eq!(begin, fold_begin);
+        assert_eq!(end, fold_end);
+        let occurrences = tz.from_local_datetime(&in_fold);
+        assert_eq!(earlier, occurrences.earliest().unwrap());
+        assert_eq!(later, occurrences.latest().unwrap());
+        assert_eq!(later - earlier, fold_end - fold_begin);
+
+        assert!(FoldInfo::new(&(fold_begin - Duration::seconds(1)), &tz).is_none());
+        assert!(FoldInfo::new(&fold_end, &tz).is_none());
+    }
+
+    #[test]
+    fn posix_tz_strings() {
+        assert_eq!(
+            Tz::America__New_York.posix_tz_string(),
+            Some("EST5EDT,M3.2.0,M11.1.0")
+        );
+        assert_eq!(
+            Tz::Europe__Dublin.posix_tz_string(),
+            Some("IST-1GMT0,M10.5.0,M3.5.0/1")
+        );
+        assert_eq!(
+            Tz::Asia__Jerusalem.posix_tz_string(),
+            Some("IST-2IDT,M3.4.4/26,M10.5.0")
+        );
+        assert_eq!(Tz::Asia__Kolkata.posix_tz_string(), Some("IST-5:30"));
+        assert_eq!(Tz::America__Sao_Paulo.posix_tz_string(), Some("<-03>3"));
+        assert_eq!(Tz::UTC.posix_tz_string(), Some("UTC0"));
+        // Links have the string of their target.
+        assert_eq!(
+            Tz::US__Eastern.posix_tz_string(),
+            Tz::America__New_York.posix_tz_string()
+        );
+    }
+
+    #[test]
+    fn links() {
+        assert_eq!(Tz::Asia__Calcutta.canonical(), Tz::Asia__Kolkata);
+        assert_eq!(Tz::Asia__Kolkata.canonical(), Tz::Asia__Kolkata);
+        assert!(Tz::Asia__Calcutta.is_link());
+        assert!(!Tz::Asia__Kolkata.is_link());
+        assert_eq!(Tz::Asia__Kolkata.aliases(), [Tz::Asia__Calcutta]);
+        assert_eq!(Tz::Asia__Calcutta.aliases(), [Tz::Asia__Kolkata]);
+        assert_eq!(Tz::Europe__Andorra.aliases(), []);
+
+        let eastern = Tz::US__Eastern.aliases();
+        assert!(eastern.contains(&Tz::America__New_York));
+        assert!(!eastern.contains(&Tz::US__Eastern));
+        assert!(Tz::America__New_York.aliases().contains(&Tz::US__Eastern));
+
+        for tz in TZ_VARIANTS {
+            let canonical = tz.canonical();
+            assert!(!canonical.is_link(), "{tz}");
+            for alias in tz.aliases() {
+                assert_eq!(alias.canonical(), canonical, "{tz} {alias}");
+            }
+        }
+    }
+
+    #[test]
+    fn equivalent_zones() {
+        use super::Equivalence::{Offset, OffsetAndAbbreviation};
+        let utc =
+            |year, month, day, hour| Utc.with_ymd_and_hms(year, month, day, hour, 0, 0).unwrap();
+
+        assert_eq!(
+            Tz::Europe__Paris.equivalent_since(Tz::Europe__Berlin, Offset),
+            Some(utc(1979, 9, 30, 1))
+        );
+        assert_eq!(
+            Tz::Europe__Berlin.equivalent_since(Tz::Europe__Paris, OffsetAndAbbreviation),
+            Some(utc(1979, 9, 30, 1))
+        );
+        // Links have always been equivalent to their zone.
+        assert_eq!(
+            Tz::US__Eastern.equivalent_since(Tz::America__New_York, OffsetAndAbbreviation),
+            Some(DateTime::<Utc>::MIN_UTC)
+        );
+        assert_eq!(Tz::Europe__Berlin.equivalent_since(London, Offset), None);
+        // Dublin uses negative daylight saving time in winter, so it has the same
+        // offsets as London but not the same abbreviations.
+        assert!(Tz::Europe__Dublin
+            .equivalent_since(London, Offset)
+            .is_some());
+        assert_eq!(
+            Tz::Europe__Dublin.equivalent_since(London, OffsetAndAbbreviation),
+            None
+        );
+
+        let range = utc(2020, 1, 1, 0)..utc(2021, 1, 1, 0);
+        assert!(Tz::Europe__Dublin.equivalent_between(London, range.clone(), Offset));
+        assert!(!Tz::Europe__Dublin.equivalent_between(
+            London,
+            range.clone(),
+            OffsetAndAbbreviation
+        ));
+        assert!(!Tz::Europe__Berlin.equivalent_between(London, range, Offset));
+        // Minsk and Moscow both stopped changing clocks in 2011, at different offsets.
+        assert!(Tz::Europe__Kyiv.equivalent_between(
+            Tz::Europe__Helsinki,
+            utc(2023, 1, 1, 0)..utc(2024, 1, 1, 0),
+            OffsetAndAbbreviation
+        ));
+        assert!(!Tz::Europe__Moscow.equivalent_between(
+            Tz::Europe__Minsk,
+            utc(2010, 1, 1, 0)..utc(2020, 1, 1, 0),
+            Offset
+        ));
+    }
+
+    #[test]
+    fn windows_ids() {
+        let id = "Pacific Standard Time";
+        assert_eq!(
+            Tz::from_windows_id(id, None),
+            Some(Tz::America__Los_Angeles)
+        );
+        assert_eq!(
+            Tz::from_windows_id(id, Some("CA")),
+            Some(Tz::America__Vancouver)
+        );
+        assert_eq!(Tz::from_windows_id(id, Some("ZZ")), Some(Tz::PST8PDT));
+        assert_eq!(
+            Tz::from_windows_id(id, Some("DE")),
+            Some(Tz::America__Los_Angeles)
+        );
+        assert_eq!(Tz::from_windows_id("pacific standard time", None), None);
+        // CLDR lists the most populous zone first when a territory has several.
+        assert_eq!(
+            Tz::from_windows_id("SA Pacific Standard Time", Some("BR")),
+            Some(Tz::America__Rio_Branco)
+        );
+
+        assert_eq!(Tz::America__Los_Angeles.windows_id(), Some(id));
+        assert_eq!(
+            Tz::Europe__Berlin.windows_id(),
+            Some("W. Europe Standard Time")
+        );
+        // CLDR uses the older names of some zones.
+        assert_eq!(Tz::Asia__Kolkata.windows_id(), Some("India Standard Time"));
+        assert_eq!(Tz::Asia__Calcutta.windows_id(), Some("India Standard Time"));
+        assert_eq!(Tz::US__Pacific.windows_id(), Some(id));
+
+        // Every Windows ID of a zone leads back, in the country of the zone, to a zone
+        // with the same offsets in 2021, the `typeVersion` of `windowsZones.xml`.
+        let year = Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap()
+            ..Utc.with_ymd_and_hms(2022, 1, 1, 0, 0, 0).unwrap();
+        for tz in TZ_VARIANTS {
+            if let Some(windows_id) = tz.windows_id() {
+                let back = Tz::from_windows_id(windows_id, tz.countries().first().copied());
+                let back = back.unwrap_or_else(|| panic!("{tz} {windows_id}"));
+                assert!(
+                    back.equivalent_between(tz, year.clone(), super::Equivalence::Offset),
+                    "{tz} {windows_id} {back}"
+                );
+            }
+        }
+    }
+
+    #[cfg(feature = "metazones")]
+    #[test]
+    fn metazones() {
+        let at =
+            |year, month, day, hour| Utc.with_ymd_and_hms(year, month, day, hour, 0, 0).unwrap();
+        let now = at(2024, 1, 1, 0);
+        assert_eq!(
+            Tz::America__Los_Angeles.metazone_at(now),
+            Some("America_Pacific")
+        );
+        assert_eq!(
+            Tz::America__Vancouver.metazone_at(now),
+            Some("America_Pacific")
+        );
+        assert_eq!(Tz::US__Pacific.metazone_at(now), Some("America_Pacific"));
+        assert_eq!(Tz::Europe__Berlin.metazone_at(now), Some("Europe_Central"));
+        assert_eq!(Tz::Asia__Kolkata.metazone_at(now), Some("India"));
+        assert_eq!(Tz::Etc__GMTPlus5.metazone_at(now), None);
+
+        // Changes take effect at the exact time that CLDR gives.
+        let knox = Tz::America__Indiana__Knox;
+        assert_eq!(
+            knox.metazone_at(at(1991, 10, 27, 6)),
+            Some("America_Central")
+        );
+        assert_eq!(
+            knox.metazone_at(at(1991, 10, 27, 7)),
+            Some("America_Eastern")
+        );
+        assert_eq!(knox.metazone_at(at(2006, 4, 2, 7)), Some("America_Central"));
+        assert_eq!(knox.metazone_at(now), Some("America_Central"));
+        assert_eq!(
+            knox.metazone_at(DateTime::<Utc>::MIN_UTC),
+            Some("America_Central")
+        );
+    }
+
+    #[cfg(feature = "localized-names")]
+    #[test]
+    fn localized_names() {
+        let offset_at = |tz: Tz, month| {
+            *UTC.with_ymd_and_hms(2024, month, 1, 12, 0, 0)
+                .unwrap()
+                .with_timezone(&tz)
+                .offset()
+        };
+
+        assert_eq!(
+            offset_at(Tz::Europe__Berlin, 7).long_name("en"),
+            Some("Central European Summer Time")
+        );
+        assert_eq!(
+            offset_at(Tz::Europe__Berlin, 1).long_name("en"),
+            Some("Central European Standard Time")
+        );
+        assert_eq!(
+            offset_at(Tz::America__Los_Angeles, 1).long_name("en-US"),
+            Some("Pacific Standard Time")
+        );
+        assert_eq!(
+            offset_at(Tz::Europe__Paris, 7).long_name("fr_FR"),
+            Some("heure d’été d’Europe centrale")
+        );
+        // The names of single zones override those of their metazone.
+        assert_eq!(
+            offset_at(Tz::Europe__London, 7).long_name("en"),
+            Some("British Summer Time")
+        );
+        assert_eq!(
+            offset_at(Tz::Europe__London, 1).long_name("en"),
+            Some("Greenwich Mean Time")
+        );
+        // Dublin has negative DST in winter, but CLDR calls summer its daylight time.
+        assert_eq!(
+            offset_at(Tz::Europe__Dublin, 7).long_name("en"),
+            Some("Irish Standard Time")
+        );
+        assert_eq!(
+            offset_at(Tz::Europe__Dublin, 1).long_name("en"),
+            Some("Greenwich Mean Time")
+        );
+        assert_eq!(
+            offset_at(Tz::UTC, 1).long_name("en"),
+            Some("Coordinated Universal Time")
+        );
+
+        assert_eq!(
+            Tz::America__Los_Angeles.generic_name("en"),
+            Some("Pacific Time")
+        );
+        assert_eq!(Tz::US__Pacific.generic_name("en"), Some("Pacific Time"));
+        assert_eq!(
+            Tz::Asia__Kolkata.generic_name("en"),
+            Some("India Standard Time")
+        );
+        assert_eq!(Tz::Asia__Tokyo.generic_name("ja"), Some("日本時間"));
+        // Unknown locales fall back to the root locale, which has no names.
+        assert_eq!(Tz::America__Los_Angeles.generic_name("xx"), None);
+
+        assert_eq!(
+            Tz::America__Los_Angeles.exemplar_city("en"),
+            Some("Los Angeles")
+        );
+        assert_eq!(
+            Tz::America__Los_Angeles.exemplar_city("xx"),
+            Some("Los Angeles")
+        );
+        assert_eq!(Tz::Asia__Kolkata.exemplar_city("en"), Some("Kolkata"));
+        assert_eq!(Tz::Asia__Calcutta.exemplar_city("en"), Some("Kolkata"));
+        assert_eq!(Tz::Europe__Moscow.exemplar_city("ru-RU"), Some("Москва"));
+        assert_eq!(
+            Tz::America__Indiana__Knox.exemplar_city("en"),
+            Some("Knox, Indiana")
+        );
+        assert_eq!(Tz::Etc__GMTPlus5.exemplar_city("en"), None);
+        assert_eq!(Tz::UTC.exemplar_city("en"), None);
+    }
+
+    #[test]
+    fn countries_and_zones() {
+        assert_eq!(
+            zones_for_country("DE"),
+            [Tz::Europe__Berlin, Tz::Europe__Busingen]
+        );
+        assert_eq!(zones_for_country("US")[0], Tz::America__New_York);
+        assert_eq!(zones_for_country("IS"), [Tz::Atlantic__Reykjavik]);
+        assert_eq!(zones_for_country("XX"), []);
+        assert_eq!(zones_for_country("de"), []);
+        assert_eq!(country("DE").map(|country| country.name), Some("Germany"));
+
+        assert_eq!(Tz::Europe__Busingen.countries(), ["DE"]);
+        assert_eq!(Tz::Europe__Zurich.countries(), ["CH", "DE", "LI"]);
+        assert_eq!(Tz::Atlantic__Reykjavik.countries(), ["IS"]);
+        assert_eq!(Tz::Africa__Abidjan.countries()[0], "CI");
+        assert!(Tz::Africa__Abidjan.countries().contains(&"IS"));
+        assert_eq!(Tz::US__Eastern.countries(), [] as [&str; 0]);
+        assert_eq!(Tz::UTC.countries(), [] as [&str; 0]);
+
+        assert_eq!(
+            Tz::America__New_York.comment(),
+            Some("Eastern (most areas)")
+        );
+        assert_eq!(Tz::Europe__Andorra.comment(), None);
+        assert_eq!(
+            Tz::America__New_York.coordinates(),
+            Some(Coordinates {
+                latitude: 146571.0 / 3600.0,
+                longitude: -266423.0 / 3600.0,
+            })
+        );
+        assert_eq!(Tz::UTC.coordinates(), None);
+
+        // Every zone of a country lists that country; only uninhabited islands have none.
+        assert_eq!(zones_for_country("BV"), []);
+        for country in COUNTRIES.iter() {
+            for tz in country.zones {
+                assert_eq!(tz.countries()[0], country.code, "{tz}");
+                assert!(tz.coordinates().is_some(), "{tz}");
+            }
+        }
+    }
+
+    #[test]
+    fn fold_info_europe_london() {
+        fold_info_test(
+            Tz::Europe__London,
+            NaiveDate::from_ymd_opt(2016, 10, 30)
+                .unwrap()
+                .and_hms_opt(1, 0, 0)
+                .unwrap(),
+            NaiveDate::from_ymd_opt(2016, 10, 30)
+                .unwrap()
+                .and_hms_opt(2, 0, 0)
+                .unwrap(),
+        );
+    }
+
+    #[test]
+    fn fold_info_europe_moscow() {
+        fold_info_test(
+            Tz::Europe__Moscow,
+            NaiveDate::from_ymd_opt(2014, 10, 26)
+                .unwrap()
+                .and_hms_opt(1, 0, 0)
+                .unwrap(),
+            NaiveDate::from_ymd_opt(2014, 10, 26)
+                .unwrap()
+                .and_hms_opt(2, 0, 0)
+                .unwrap(),
+        );
+    }
+
+    #[test]
+    fn fold_info_lord_howe_half_hour() {
+        fold_info_test(
+            Tz::Australia__Lord_Howe,
+            NaiveDate::from_ymd_opt(2024, 4, 7)
+                .unwrap()
+                .and_hms_opt(1, 30, 0)
+                .unwrap(),
+            NaiveDate::from_ymd_opt(2024, 4, 7)
+                .unwrap()
+                .and_hms_opt(2, 0, 0)
+                .unwrap(),
+        );
+    }
+
+    #[test]
+    fn fold_info_after_2099() {
+        fold_info_test(
+            Tz::America__New_York,
+            NaiveDate::from_ymd_opt(2150, 11, 1)
+                .unwrap()
+                .and_hms_opt(1, 0, 0)
+                .unwrap(),
+            NaiveDate::from_ymd_opt(2150, 11, 1)
+                .unwrap()
+                .and_hms_opt(2, 0, 0)
+                .unwrap(),
+        );
+    }
+
+    #[test]
+    fn fold_info_keeps_fractions_of_seconds() {
+        let local = NaiveDate::from_ymd_opt(2025, 10, 26)
+            .unwrap()
+            .and_hms_milli_opt(1, 30, 0, 500)
+            .unwrap();
+        let fold = FoldInfo::new(&local, &London).unwrap();
+        let occurrences = London.from_local_datetime(&local);
+        assert_eq!(fold.earlier, occurrences.earliest().unwrap());
+        assert_eq!(fold.later, occurrences.latest().unwrap());
+        assert_eq!(fold.earlier.naive_local(), local);
+        assert_eq!(fold.later.naive_local(), local);
+    }
+
+    #[test]
+    fn fold_info_outside_folds() {
+        let gap = NaiveDate::from_ymd_opt(2016, 3, 27)
+            .unwrap()
+            .and_hms_opt(1, 30, 0)
+            .unwrap();
+        assert!(FoldInfo::new(&gap, &Tz::Europe__London).is_none());
+        assert!(FoldInfo::new(&gap, &Tz::UTC).is_none());
+    }
+
+    fn resolve(tz: Tz, local: NaiveDateTime, disambiguation: Disambiguation) -> String {
+        match tz.resolve_local(&local, disambiguation) {
+            Ok(dt) => dt.to_string(),
+            Err(err) => format!("{err:?}"),
+        }
+    }
+
+    #[test]
+    fn resolve_skipped_local_time() {
+        let local = NaiveDate::from_ymd_opt(2016, 3, 27)
+            .unwrap()
+            .and_hms_opt(1, 30, 0)
+            .unwrap();
+        let expected = [
+            (Disambiguation::Compatible, "2016-03-27 02:30:00 BST"),
+            (Disambiguation::Earlier, "2016-03-27 00:30:00 GMT"),
+            (Disambiguation::Later, "2016-03-27 02:30:00 BST"),
+            (Disambiguation::Reject, "Skipped"),
+        ];
+        for (disambiguation, expected) in expected {
+            assert_eq!(resolve(London, local, disambiguation), expected);
+        }
+
+        let local = NaiveDate::from_ymd_opt(2011, 12, 30)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap();
+        assert_eq!(
+            resolve(Apia, local, Disambiguation::Compatible),
+            "2011-12-31 12:00:00 +14"
+        );
+        assert_eq!(
+            resolve(Apia, local, Disambiguation::Earlier),
+            "2011-12-29 12:00:00 -10"
+        );
+
+        let local = NaiveDate::from_ymd_opt(2150, 3, 8)
+            .unwrap()
+            .and_hms_opt(2, 30, 0)
+            .unwrap();
+        assert_eq!(
+            resolve(Eastern, local, Disambiguation::Compatible),
+            "2150-03-08 03:30:00 EDT"
+        );
+    }
+
+    #[test]
+    fn resolve_repeated_local_time() {
+        let local = NaiveDate::from_ymd_opt(2016, 10, 30)
+            .unwrap()
+            .and_hms_opt(1, 30, 0)
+            .unwrap();
+        let expected = [
+            (Disambiguation::Compatible, "2016-10-30 01:30:00 BST"),
+            (Disambiguation::Earlier, "2016-10-30 01:30:00 BST"),
+            (Disambiguation::Later, "2016-10-30 01:30:00 GMT"),
+            (Disambiguation::Reject, "Ambiguous"),
+        ];
+        for (disambiguation, expected) in expected {
+            assert_eq!(resolve(London, local, disambiguation), expected);
+        }
+
+        let local = NaiveDate::from_ymd_opt(2016, 10, 30)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap();
+        assert_eq!(
+            resolve(London, local, Disambiguation::Reject),
+            "2016-10-30 12:00:00 GMT"
+        );
+    }
+
     #[test]
     fn casey_utc_change_time() {
         assert_eq!(
@@ -650,4 +1217,315 @@ mod tests {
             "+08"
         );
     }
+
+    #[test]
+    fn dst_after_2099() {
+        let summer = Eastern.with_ymd_and_hms(2150, 7, 1, 12, 0, 0).unwrap();
+        assert_eq!(summer.offset().to_string(), "EDT");
+        let winter = Eastern.with_ymd_and_hms(2150, 1, 1, 12, 0, 0).unwrap();
+        assert_eq!(winter.offset().to_string(), "EST");
+
+        let summer = Adelaide.with_ymd_and_hms(3000, 1, 1, 12, 0, 0).unwrap();
+        assert_eq!(summer.offset().to_string(), "ACDT");
+        let winter = Adelaide.with_ymd_and_hms(3000, 7, 1, 12, 0, 0).unwrap();
+        assert_eq!(winter.offset().to_string(), "ACST");
+
+        let summer = Tz::Europe__Dublin.with_ymd_and_hms(2500, 7, 1, 12, 0, 0);
+        assert_eq!(summer.unwrap().offset().to_string(), "IST");
+        let winter = Tz::Europe__Dublin.with_ymd_and_hms(2500, 1, 1, 12, 0, 0);
+        assert_eq!(winter.unwrap().offset().to_string(), "GMT");
+    }
+
+    #[test]
+    fn transitions_after_2099() {
+        let before = UTC.with_ymd_and_hms(2150, 3, 8, 6, 59, 59).unwrap();
+        assert_eq!(
+            before.with_timezone(&Eastern).to_string(),
+            "2150-03-08 01:59:59 EST"
+        );
+        let after = before + Duration::seconds(1);
+        assert_eq!(
+            after.with_timezone(&Eastern).to_string(),
+            "2150-03-08 03:00:00 EDT"
+        );
+
+        assert!(Eastern
+            .with_ymd_and_hms(2150, 3, 8, 2, 30, 0)
+            .single()
+            .is_none());
+        let ambiguous = Eastern.with_ymd_and_hms(2150, 11, 1, 1, 30, 0);
+        assert_eq!(
+            ambiguous.earliest().unwrap(),
+            UTC.with_ymd_and_hms(2150, 11, 1, 5, 30, 0).unwrap()
+        );
+        assert_eq!(
+            ambiguous.latest().unwrap(),
+            UTC.with_ymd_and_hms(2150, 11, 1, 6, 30, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn transitions_both_ways() {
+        let start = Utc.with_ymd_and_hms(2015, 6, 1, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2017, 3, 26, 1, 0, 0).unwrap();
+        let expected = [
+            (
+                Utc.with_ymd_and_hms(2015, 10, 25, 1, 0, 0).unwrap(),
+                "BST",
+                "GMT",
+            ),
+            (
+                Utc.with_ymd_and_hms(2016, 3, 27, 1, 0, 0).unwrap(),
+                "GMT",
+                "BST",
+            ),
+            (
+                Utc.with_ymd_and_hms(2016, 10, 30, 1, 0, 0).unwrap(),
+                "BST",
+                "GMT",
+            ),
+        ];
+
+        let forwards = London
+            .transitions_between(start, end)
+            .map(|t| (t.at, t.before.to_string(), t.after.to_string()))
+            .collect::<Vec<_>>();
+        let backwards = London
+            .transitions_between(start, end)
+            .rev()
+            .map(|t| (t.at, t.before.to_string(), t.after.to_string()))
+            .collect::<Vec<_>>();
+        assert_eq!(forwards.len(), expected.len());
+        for (((at, before, after), forward), backward) in
+            expected.iter().zip(&forwards).zip(backwards.iter().rev())
+        {
+            assert_eq!(forward, &(*at, before.to_string(), after.to_string()));
+            assert_eq!(backward, forward);
+        }
+
+        let mut iter = London.transitions_between(start, end);
+        assert_eq!(iter.next().unwrap().at, expected[0].0);
+        assert_eq!(iter.next_back().unwrap().at, expected[2].0);
+        assert_eq!(iter.next().unwrap().at, expected[1].0);
+        assert!(iter.next_back().is_none());
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn transitions_across_2100() {
+        let start = Utc.with_ymd_and_hms(2099, 1, 1, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2101, 1, 1, 0, 0, 0).unwrap();
+        let forwards = Eastern
+            .transitions_between(start, end)
+            .map(|t| (t.at, t.after.to_string()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            forwards,
+            [
+                (
+                    Utc.with_ymd_and_hms(2099, 3, 8, 7, 0, 0).unwrap(),
+                    "EDT".to_string()
+                ),
+                (
+                    Utc.with_ymd_and_hms(2099, 11, 1, 6, 0, 0).unwrap(),
+                    "EST".to_string()
+                ),
+                (
+                    Utc.with_ymd_and_hms(2100, 3, 14, 7, 0, 0).unwrap(),
+                    "EDT".to_string()
+                ),
+                (
+                    Utc.with_ymd_and_hms(2100, 11, 7, 6, 0, 0).unwrap(),
+                    "EST".to_string()
+                ),
+            ]
+        );
+        let backwards = Eastern
+            .transitions_between(start, end)
+            .rev()
+            .map(|t| (t.at, t.after.to_string()))
+            .collect::<Vec<_>>();
+        assert_eq!(backwards.into_iter().rev().collect::<Vec<_>>(), forwards);
+    }
+
+    #[test]
+    fn next_and_previous_transition() {
+        let transition = Utc.with_ymd_and_hms(2016, 3, 27, 1, 0, 0).unwrap();
+        let just_before = transition - Duration::milliseconds(500);
+        let just_after = transition + Duration::milliseconds(500);
+
+        let next = London.next_transition(just_before).unwrap();
+        assert_eq!(next.at, transition);
+        assert_eq!(next.before.to_string(), "GMT");
+        assert_eq!(next.after.to_string(), "BST");
+        assert_eq!(London.previous_transition(just_after), Some(next));
+
+        let next = London.next_transition(transition).unwrap();
+        assert_eq!(
+            next.at,
+            Utc.with_ymd_and_hms(2016, 10, 30, 1, 0, 0).unwrap()
+        );
+        let previous = London.previous_transition(transition).unwrap();
+        assert_eq!(
+            previous.at,
+            Utc.with_ymd_and_hms(2015, 10, 25, 1, 0, 0).unwrap()
+        );
+
+        let far_future = Utc.with_ymd_and_hms(2500, 1, 1, 0, 0, 0).unwrap();
+        let next = Eastern.next_transition(far_future).unwrap();
+        assert_eq!(next.at, Utc.with_ymd_and_hms(2500, 3, 14, 7, 0, 0).unwrap());
+        let previous = Eastern.previous_transition(far_future).unwrap();
+        assert_eq!(
+            previous.at,
+            Utc.with_ymd_and_hms(2499, 11, 1, 6, 0, 0).unwrap()
+        );
+
+        let first = London.transitions().next().unwrap();
+        assert_eq!(London.previous_transition(first.at), None);
+        assert_eq!(UTC.next_transition(far_future), None);
+    }
+
+    #[test]
+    fn transitions_of_fixed_zones() {
+        assert_eq!(UTC.transitions().count(), 0);
+        assert_eq!(Tz::Etc__GMTPlus5.transitions().next_back(), None);
+        let last = Tz::Asia__Tokyo.transitions().next_back().unwrap();
+        assert_eq!(last.after.to_string(), "JST");
+        assert!(Eastern.transitions().next_back().unwrap().at.year() > 200_000);
+    }
+
+    #[test]
+    fn extra_rule_matches_last_transitions() {
+        // Palestine has irregular transitions until 2086, so only compare the last decade.
+        let start = UTC
+            .with_ymd_and_hms(2090, 1, 1, 0, 0, 0)
+            .unwrap()
+            .timestamp();
+        for tz in TZ_VARIANTS {
+            let timespans = tz.timespans();
+            let Some(extra) = timespans.extra else {
+                continue;
+            };
+            for (at, timespan) in timespans.rest.iter().filter(|(at, _)| *at >= start) {
+                assert!(extra.timespan_at(at) == timespan, "{tz} at {at}");
+                assert!(extra.timespan_at(at - 1) != timespan, "{tz} before {at}");
+            }
+        }
+    }
+
+    #[test]
+    fn identical_timespans_share_tables() {
+        use crate::compact::CompactTimespans;
+        use crate::timezone_impl::TransitionTable;
+
+        let table = |tz: Tz| {
+            let TransitionTable::Compact(table) = tz.timespans().rest else {
+                panic!("{tz} is not compact");
+            };
+            table as *const CompactTimespans
+        };
+        let rule = |tz: Tz| {
+            tz.timespans().extra.map(|extra| {
+                let daylight = extra
+                    .daylight
+                    .map(|rule| (rule.timespan, rule.start, rule.end));
+                (extra.standard, daylight)
+            })
+        };
+        let same = |a: Tz, b: Tz| {
+            let (a_timespans, b_timespans) = (a.timespans(), b.timespans());
+            a_timespans.first == b_timespans.first
+                && a_timespans.rest.iter().eq(b_timespans.rest.iter())
+                && rule(a) == rule(b)
+        };
+
+        // Zones share their tables exactly when they have the same timespans.
+        let mut distinct = Vec::<Tz>::new();
+        for tz in TZ_VARIANTS {
+            match distinct.iter().find(|&&other| same(tz, other)) {
+                Some(&other) => assert_eq!(table(tz), table(other), "{tz} and {other}"),
+                None => {
+                    assert!(
+                        distinct.iter().all(|&other| table(tz) != table(other)),
+                        "{tz}"
+                    );
+                    distinct.push(tz);
+                }
+            }
+        }
+
+        // `bin/test-year-range.sh` keeps the years in which Berlin and Paris agree.
+        if option_env!("CHRONO_TZ_YEARS") == Some("2000..2040") {
+            assert_eq!(table(Tz::Europe__Berlin), table(Tz::Europe__Paris));
+        }
+    }
+
+    #[test]
+    fn compact_encoding() {
+        use crate::compact::CompactTimespans;
+        use crate::timezone_impl::{FixedTimespan, TransitionTable};
+        use core::mem::size_of;
+        use parse_zoneinfo::line::Line;
+        use parse_zoneinfo::table::TableBuilder;
+        use parse_zoneinfo::transitions::TableTransitions;
+
+        // The timespans that chrono-tz-build encodes, straight from the tz database.
+        let mut builder = TableBuilder::new();
+        for file in parse_zoneinfo::FILES {
+            let path = format!("{}/tz/{file}", env!("CARGO_MANIFEST_DIR"));
+            for line in std::fs::read_to_string(path).unwrap().lines() {
+                let line = line.split('#').next().unwrap();
+                builder.add_line(Line::new(line).unwrap()).unwrap();
+            }
+        }
+        let database = builder.build();
+        let decoded_timespan = |timespan: FixedTimespan| {
+            let name = timespan.name.map(str::to_owned);
+            (timespan.utc_offset as i64, timespan.dst_offset as i64, name)
+        };
+
+        // Links share the tables of their zone, so count each of them once.
+        let mut seen: Vec<*const CompactTimespans> = Vec::new();
+        let (mut compact, mut plain) = (0, 0);
+        for tz in TZ_VARIANTS {
+            let expected = database.timespans(tz.name()).unwrap();
+            let expected_timespan = |timespan: &parse_zoneinfo::transitions::FixedTimespan| {
+                let name = Some(timespan.name.clone());
+                (timespan.utc_offset, timespan.dst_offset, name)
+            };
+            let timespans = tz.timespans();
+            assert_eq!(
+                decoded_timespan(timespans.first),
+                expected_timespan(&expected.first),
+                "{tz}"
+            );
+            let decoded = timespans
+                .rest
+                .iter()
+                .map(|(at, timespan)| (at, decoded_timespan(timespan)));
+            let expected_rest = expected
+                .rest
+                .iter()
+                .map(|(at, timespan)| (*at, expected_timespan(timespan)));
+            assert!(decoded.eq(expected_rest), "{tz}");
+
+            let TransitionTable::Compact(table) = timespans.rest else {
+                panic!("{tz} is not compact");
+            };
+            if seen.contains(&(table as *const _)) {
+                continue;
+            }
+            seen.push(table);
+            compact += table.size();
+            plain += size_of::<FixedTimespan>() + table.len() * size_of::<(i64, FixedTimespan)>();
+        }
+
+        // With tz database 2025b, the compact tables take 183,336 bytes against
+        // 1,163,552 for plain ones, 6.35 times less.
+        let ratio = plain as f64 / compact as f64;
+        assert!(
+            compact * 63 < plain * 10,
+            "{compact} bytes against {plain} in plain tables, only {ratio:.2} times smaller"
+        );
+    }
 }
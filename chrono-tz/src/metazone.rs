@@ -0,0 +1,71 @@
+//! Metazones, from the `supplemental/metaZones.json` file of the Unicode CLDR that is
+//! vendored in `cldr/`.
+//!
+//! The tables are only compiled in with the `metazones` feature.
+
+use chrono::{DateTime, Utc};
+
+use crate::timezones::Tz;
+
+/// The metazones that a zone has been in, in the same shape as its timespans.
+pub struct MetazoneSet {
+    /// The metazone before the first change, if any.
+    pub first: Option<&'static str>,
+    /// The times at which the zone changed metazone, with the metazone after the
+    /// change. `None` when the zone left its metazone for no other.
+    pub rest: &'static [(i64, Option<&'static str>)],
+}
+
+impl MetazoneSet {
+    fn at(&self, timestamp: i64) -> Option<&'static str> {
+        match self
+            .rest
+            .binary_search_by(|&(start, _)| start.cmp(&timestamp))
+        {
+            Ok(index) => self.rest[index].1,
+            Err(0) => self.first,
+            Err(index) => self.rest[index - 1].1,
+        }
+    }
+
+    /// The metazone that the zone is in now and, as far as CLDR knows, from now on.
+    #[cfg(feature = "localized-names")]
+    pub(crate) fn current(&self) -> Option<&'static str> {
+        self.rest
+            .last()
+            .map_or(self.first, |&(_, metazone)| metazone)
+    }
+}
+
+pub trait Metazones {
+    fn metazones(&self) -> MetazoneSet;
+}
+
+include!(concat!(env!("OUT_DIR"), "/metazones.rs"));
+
+impl Tz {
+    /// The CLDR metazone that this timezone was in at `at`, such as `America_Pacific`
+    /// for `America/Los_Angeles` and `America/Vancouver`.
+    ///
+    /// A metazone groups the zones that keep the same clocks during some period,
+    /// regardless of their country, so that they can share their names. Zones move
+    /// between metazones when they change their rules: `America/Indiana/Knox` has
+    /// been in `America_Central` since 2006, but was in `America_Eastern` before
+    /// that. Returns `None` for zones that are in no metazone at `at`, such as
+    /// `Etc/GMT+5`.
+    ///
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// use chrono_tz::Tz;
+    ///
+    /// let now = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    /// assert_eq!(Tz::America__Los_Angeles.metazone_at(now), Some("America_Pacific"));
+    /// assert_eq!(Tz::America__Vancouver.metazone_at(now), Some("America_Pacific"));
+    ///
+    /// let before = Utc.with_ymd_and_hms(2006, 1, 1, 0, 0, 0).unwrap();
+    /// assert_eq!(Tz::America__Indiana__Knox.metazone_at(before), Some("America_Eastern"));
+    /// ```
+    pub fn metazone_at(self, at: DateTime<Utc>) -> Option<&'static str> {
+        self.metazones().at(at.timestamp())
+    }
+}
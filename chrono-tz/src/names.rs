@@ -0,0 +1,149 @@
+//! Localized names of timezones, from the `timeZoneNames.json` files of the Unicode
+//! CLDR that are vendored in `cldr/`.
+//!
+//! CLDR gives most names to metazones, such as `America_Pacific`, which group the
+//! zones that share their clocks now. Names of single zones override those.
+
+use core::iter;
+
+use chrono::Offset;
+
+use crate::metazone::Metazones;
+use crate::timezone_impl::{OffsetComponents, TzOffset};
+use crate::timezones::Tz;
+
+/// The names of zones and metazones in one locale, keyed by CLDR zone ID.
+struct LocaleNames {
+    locale: &'static str,
+    /// The index in `LOCALES` of the locale to fall back to.
+    parent: Option<usize>,
+    exemplar_cities: phf::Map<&'static str, &'static str>,
+    zones: phf::Map<&'static str, LongNames>,
+    metazones: phf::Map<&'static str, LongNames>,
+}
+
+struct LongNames {
+    generic: Option<&'static str>,
+    standard: Option<&'static str>,
+    daylight: Option<&'static str>,
+}
+
+include!(concat!(env!("OUT_DIR"), "/names.rs"));
+
+/// The names for `locale`, followed by those of the locales that it falls back to,
+/// ending with the root locale. A tag that isn't compiled in is truncated until it
+/// is, so `en-GB` uses the names of `en`.
+fn locales(locale: &str) -> impl Iterator<Item = &'static LocaleNames> {
+    let same_tag = |a: &str, b: &str| {
+        a.len() == b.len()
+            && a.bytes().zip(b.bytes()).all(|(a, b)| {
+                a.eq_ignore_ascii_case(&b) || (a == b'-' && b == b'_') || (a == b'_' && b == b'-')
+            })
+    };
+    let mut tag = locale;
+    let first = loop {
+        if let Some(index) = LOCALES.iter().position(|names| same_tag(names.locale, tag)) {
+            break Some(index);
+        }
+        match tag.rfind(['-', '_']) {
+            Some(end) => tag = &tag[..end],
+            None => break LOCALES.iter().position(|names| names.locale == "und"),
+        }
+    };
+    iter::successors(first, |&index| LOCALES[index].parent).map(|index| &LOCALES[index])
+}
+
+/// The ID that CLDR uses for `tz`, such as `Asia/Calcutta` for `Asia/Kolkata`.
+fn cldr_id(tz: Tz) -> &'static str {
+    CLDR_IDS.get(tz.name()).copied().unwrap_or(tz.name())
+}
+
+/// Looks up one of the long names of `tz`: first for the zone itself in `locale` and
+/// its fallbacks, then for its current metazone.
+fn long_name(
+    tz: Tz,
+    locale: &str,
+    name: fn(&LongNames) -> Option<&'static str>,
+) -> Option<&'static str> {
+    let id = cldr_id(tz);
+    let zone = locales(locale).find_map(|names| names.zones.get(id).and_then(name));
+    zone.or_else(|| {
+        let metazone = tz.metazones().current()?;
+        locales(locale).find_map(|names| names.metazones.get(metazone).and_then(name))
+    })
+}
+
+impl Tz {
+    /// The name of the city that represents this timezone in `locale`, such as
+    /// `Los Angeles` for `America/Los_Angeles`, or `Москва` in Russian for
+    /// `Europe/Moscow`.
+    ///
+    /// `locale` is a BCP 47 language tag such as `en` or `pt-BR`; tags without
+    /// names of their own use those of the language. Returns `None` for zones
+    /// that don't represent a city, such as `Etc/GMT+5`.
+    ///
+    /// ```
+    /// use chrono_tz::Tz;
+    ///
+    /// assert_eq!(Tz::America__Los_Angeles.exemplar_city("en"), Some("Los Angeles"));
+    /// assert_eq!(Tz::Europe__Moscow.exemplar_city("ru"), Some("Москва"));
+    /// assert_eq!(Tz::Asia__Kolkata.exemplar_city("en"), Some("Kolkata"));
+    /// ```
+    pub fn exemplar_city(self, locale: &str) -> Option<&'static str> {
+        let id = cldr_id(self);
+        locales(locale).find_map(|names| names.exemplar_cities.get(id).copied())
+    }
+
+    /// The name of this timezone in `locale` that doesn't depend on the time of
+    /// year, such as `Pacific Time` for `America/Los_Angeles`.
+    ///
+    /// For zones without daylight saving time, this is their standard name, such
+    /// as `India Standard Time`. Returns `None` for zones that CLDR names by their
+    /// country or city only.
+    ///
+    /// ```
+    /// use chrono_tz::Tz;
+    ///
+    /// assert_eq!(Tz::America__Los_Angeles.generic_name("en"), Some("Pacific Time"));
+    /// assert_eq!(Tz::Europe__Paris.generic_name("fr"), Some("heure d’Europe centrale"));
+    /// ```
+    pub fn generic_name(self, locale: &str) -> Option<&'static str> {
+        long_name(self, locale, |names| names.generic).or_else(|| {
+            match long_name(self, locale, |names| names.daylight) {
+                Some(_) => None,
+                None => long_name(self, locale, |names| names.standard),
+            }
+        })
+    }
+}
+
+impl TzOffset {
+    /// The name of this offset in `locale`, such as `Central European Summer Time`
+    /// in July and `Central European Standard Time` in January for `Europe/Berlin`.
+    ///
+    /// Names are those of the metazone that the zone uses now, so this can be
+    /// wrong for offsets that the zone no longer uses. See
+    /// [`Tz::exemplar_city()`] for the forms of `locale`.
+    ///
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// use chrono_tz::Europe::Berlin;
+    ///
+    /// let summer = Utc.with_ymd_and_hms(2024, 7, 1, 12, 0, 0).unwrap();
+    /// let offset = *summer.with_timezone(&Berlin).offset();
+    /// assert_eq!(offset.long_name("en"), Some("Central European Summer Time"));
+    /// ```
+    pub fn long_name(&self, locale: &str) -> Option<&'static str> {
+        let tz = self.tz();
+        // The offset of zones with negative DST is zero in summer.
+        let dst = self.dst_offset().num_seconds();
+        let daylight = match NEGATIVE_DST.get(cldr_id(tz)) {
+            Some(&standard) if dst == 0 => self.fix().local_minus_utc() > standard,
+            _ => dst > 0,
+        };
+        match daylight {
+            true => long_name(tz, locale, |names| names.daylight),
+            false => long_name(tz, locale, |names| names.standard),
+        }
+    }
+}
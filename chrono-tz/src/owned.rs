@@ -0,0 +1,152 @@
+//! The timespans of the zones that are read at runtime
+//!
+//! Like a TZif file, a zone keeps each of its distinct timespans once, and each
+//! distinct abbreviation once, so that the zone owns them and frees them with itself.
+//! Transitions refer to the timespans, and timespans to the abbreviations, by index.
+
+use crate::timezone_impl::{
+    DaylightRule, ExtraRule, FixedTimespan, FixedTimespanSet, RuleTransition, TransitionTable,
+};
+
+/// The timespans of a zone, which own their abbreviations.
+pub(crate) struct OwnedTimespans {
+    /// The distinct abbreviations of the zone.
+    names: Box<[Box<str>]>,
+    /// The distinct timespans of the zone.
+    types: Box<[LocalType]>,
+    /// The index in `types` of the timespan before the first transition.
+    first: usize,
+    /// The transition times, each with the index in `types` of the timespan that it
+    /// starts.
+    transitions: Box<[(i64, usize)]>,
+    /// The rule that keeps producing timespans after the last transition.
+    extra: Option<OwnedRule>,
+}
+
+/// A timespan of an [`OwnedTimespans`], whose abbreviation is an index into its names.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub(crate) struct LocalType {
+    utc_offset: i32,
+    dst_offset: i32,
+    name: Option<usize>,
+}
+
+/// An [`ExtraRule`] with the timespans of an [`OwnedTimespans`].
+struct OwnedRule {
+    standard: LocalType,
+    daylight: Option<(LocalType, RuleTransition, RuleTransition)>,
+}
+
+impl OwnedTimespans {
+    /// Copies the timespans of a zone that starts with `first` and switches to the
+    /// timespans of `rest` at their timestamps.
+    pub(crate) fn new(
+        first: FixedTimespan,
+        rest: &[(i64, FixedTimespan)],
+        extra: Option<ExtraRule>,
+    ) -> Self {
+        let mut builder = Builder::default();
+        let first = builder.type_index(first);
+        let transitions = rest
+            .iter()
+            .map(|&(at, timespan)| (at, builder.type_index(timespan)))
+            .collect();
+        let extra = extra.map(|extra| OwnedRule {
+            standard: builder.local_type(extra.standard),
+            daylight: extra.daylight.map(|daylight| {
+                let timespan = builder.local_type(daylight.timespan);
+                (timespan, daylight.start, daylight.end)
+            }),
+        });
+        OwnedTimespans {
+            names: builder.names.into(),
+            types: builder.types.into(),
+            first,
+            transitions,
+            extra,
+        }
+    }
+
+    pub(crate) fn timespans(&self) -> FixedTimespanSet<'_> {
+        FixedTimespanSet {
+            first: self.timespan(self.types[self.first]),
+            rest: TransitionTable::Owned(self),
+            extra: self.extra.as_ref().map(|extra| ExtraRule {
+                standard: self.timespan(extra.standard),
+                daylight: extra.daylight.map(|(timespan, start, end)| DaylightRule {
+                    timespan: self.timespan(timespan),
+                    start,
+                    end,
+                }),
+            }),
+        }
+    }
+
+    /// The number of transitions.
+    pub(crate) fn len(&self) -> usize {
+        self.transitions.len()
+    }
+
+    /// The transition at `index`, with the timespan that it starts.
+    pub(crate) fn get(&self, index: usize) -> (i64, FixedTimespan<'_>) {
+        let (at, local_type) = self.transitions[index];
+        (at, self.timespan(self.types[local_type]))
+    }
+
+    /// The timespan of `local_type`, with its abbreviation.
+    pub(crate) fn timespan(&self, local_type: LocalType) -> FixedTimespan<'_> {
+        FixedTimespan {
+            utc_offset: local_type.utc_offset,
+            dst_offset: local_type.dst_offset,
+            name: local_type.name.map(|index| &*self.names[index]),
+        }
+    }
+
+    /// `timespan`, which has to be one of this zone, without borrowing its abbreviation.
+    pub(crate) fn local_type(&self, timespan: FixedTimespan) -> LocalType {
+        LocalType {
+            utc_offset: timespan.utc_offset,
+            dst_offset: timespan.dst_offset,
+            name: timespan
+                .name
+                .and_then(|name| self.names.iter().position(|known| **known == *name)),
+        }
+    }
+}
+
+#[derive(Default)]
+struct Builder {
+    names: Vec<Box<str>>,
+    types: Vec<LocalType>,
+}
+
+impl Builder {
+    fn type_index(&mut self, timespan: FixedTimespan) -> usize {
+        let local_type = self.local_type(timespan);
+        match self.types.iter().position(|&known| known == local_type) {
+            Some(index) => index,
+            None => {
+                self.types.push(local_type);
+                self.types.len() - 1
+            }
+        }
+    }
+
+    fn local_type(&mut self, timespan: FixedTimespan) -> LocalType {
+        let name =
+            timespan.name.map(
+                |name| match self.names.iter().position(|known| **known == *name) {
+                    Some(index) => index,
+                    None => {
+                        self.names.push(name.into());
+                        self.names.len() - 1
+                    }
+                },
+            );
+        LocalType {
+            utc_offset: timespan.utc_offset,
+            dst_offset: timespan.dst_offset,
+            name,
+        }
+    }
+}
@@ -0,0 +1,384 @@
+//! Time zones described by POSIX TZ strings, such as `EST5EDT,M3.2.0,M11.1.0`
+//!
+//! These are found in the `TZ` environment variable and in the footer of TZif files.
+//! Besides the POSIX syntax, this accepts the extensions of RFC 8536: quoted
+//! abbreviations like `<+03>`, and transition times that are negative or larger than
+//! 24 hours.
+
+use core::fmt::{self, Debug, Display, Formatter};
+use core::str::FromStr;
+use std::sync::Arc;
+
+use chrono::{
+    Duration, FixedOffset, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, Offset, TimeZone,
+};
+
+use crate::owned::{LocalType, OwnedTimespans};
+use crate::timezone_impl::{
+    DaylightRule, ExtraRule, FixedTimespan, FixedTimespanSet, RuleDate, RuleTransition, TimeSpans,
+};
+use crate::{OffsetComponents, OffsetName};
+
+/// A time zone described by a POSIX TZ string.
+///
+/// The string gives a standard time and optionally a daylight saving time, with
+/// the yearly rules to switch between them, as in `CET-1CEST,M3.5.0,M10.5.0/3`.
+/// Offsets count hours west of Greenwich, so `CET-1` is an hour ahead of UTC.
+///
+/// ```
+/// # extern crate chrono;
+/// # extern crate chrono_tz;
+/// use chrono::TimeZone;
+/// use chrono_tz::{OffsetComponents, PosixTz};
+///
+/// # fn main() {
+/// let tz: PosixTz = "CET-1CEST,M3.5.0,M10.5.0/3".parse().unwrap();
+/// let summer = tz.with_ymd_and_hms(2024, 7, 1, 12, 0, 0).unwrap();
+/// assert_eq!(summer.to_string(), "2024-07-01 12:00:00 CEST");
+/// assert_eq!(summer.offset().dst_offset(), chrono::Duration::hours(1));
+///
+/// let tz: PosixTz = "<+03>-3".parse().unwrap();
+/// let dt = tz.with_ymd_and_hms(2024, 7, 1, 12, 0, 0).unwrap();
+/// assert_eq!(dt.to_rfc3339(), "2024-07-01T12:00:00+03:00");
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct PosixTz(Arc<Rule>);
+
+struct Rule {
+    tz_string: Box<str>,
+    timespans: OwnedTimespans,
+}
+
+impl PosixTz {
+    /// Parses a POSIX TZ string.
+    pub fn parse(tz_string: &str) -> Result<Self, PosixTzError> {
+        let rule = parse(tz_string).ok_or(PosixTzError(()))?;
+        Ok(PosixTz(Arc::new(Rule {
+            tz_string: tz_string.into(),
+            timespans: OwnedTimespans::new(rule.standard, &[], Some(rule)),
+        })))
+    }
+
+    /// The TZ string that this time zone was parsed from.
+    pub fn tz_string(&self) -> &str {
+        &self.0.tz_string
+    }
+}
+
+impl FromStr for PosixTz {
+    type Err = PosixTzError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        PosixTz::parse(s)
+    }
+}
+
+impl TimeSpans for PosixTz {
+    fn timespans(&self) -> FixedTimespanSet<'_> {
+        self.0.timespans.timespans()
+    }
+}
+
+impl Debug for PosixTz {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.tz_string())
+    }
+}
+
+impl Display for PosixTz {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.tz_string())
+    }
+}
+
+impl TimeZone for PosixTz {
+    type Offset = PosixTzOffset;
+
+    fn from_offset(offset: &Self::Offset) -> Self {
+        offset.tz.clone()
+    }
+
+    #[allow(deprecated)]
+    fn offset_from_local_date(&self, local: &NaiveDate) -> LocalResult<Self::Offset> {
+        self.timespans()
+            .timespan_at_local_date(local)
+            .map(|offset| PosixTzOffset::new(self.clone(), offset))
+    }
+
+    fn offset_from_local_datetime(&self, local: &NaiveDateTime) -> LocalResult<Self::Offset> {
+        self.timespans()
+            .timespans_at_local(local.and_utc().timestamp())
+            .map(|offset| PosixTzOffset::new(self.clone(), offset))
+    }
+
+    #[allow(deprecated)]
+    fn offset_from_utc_date(&self, utc: &NaiveDate) -> Self::Offset {
+        self.offset_from_utc_datetime(&utc.and_time(NaiveTime::MIN))
+    }
+
+    fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> Self::Offset {
+        let timestamp = utc.and_utc().timestamp();
+        PosixTzOffset::new(self.clone(), self.timespans().timespan_at_utc(timestamp))
+    }
+}
+
+/// The offset of a [`PosixTz`] at some instant.
+#[derive(Clone)]
+pub struct PosixTzOffset {
+    tz: PosixTz,
+    offset: LocalType,
+}
+
+impl PosixTzOffset {
+    fn new(tz: PosixTz, offset: FixedTimespan) -> Self {
+        let offset = tz.0.timespans.local_type(offset);
+        PosixTzOffset { tz, offset }
+    }
+
+    fn timespan(&self) -> FixedTimespan<'_> {
+        self.tz.0.timespans.timespan(self.offset)
+    }
+}
+
+impl OffsetComponents for PosixTzOffset {
+    fn base_utc_offset(&self) -> Duration {
+        Duration::seconds(self.timespan().utc_offset as i64)
+    }
+
+    fn dst_offset(&self) -> Duration {
+        Duration::seconds(self.timespan().dst_offset as i64)
+    }
+}
+
+impl OffsetName for PosixTzOffset {
+    /// The TZ string of the time zone, as there is no IANA identifier.
+    fn tz_id(&self) -> &str {
+        self.tz.tz_string()
+    }
+
+    fn abbreviation(&self) -> Option<&str> {
+        self.timespan().name
+    }
+}
+
+impl Offset for PosixTzOffset {
+    fn fix(&self) -> FixedOffset {
+        self.timespan().fix()
+    }
+}
+
+impl Display for PosixTzOffset {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.timespan(), f)
+    }
+}
+
+impl Debug for PosixTzOffset {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.timespan(), f)
+    }
+}
+
+/// The error returned when parsing an invalid POSIX TZ string.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PosixTzError(());
+
+impl Display for PosixTzError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid POSIX TZ string")
+    }
+}
+
+impl std::error::Error for PosixTzError {}
+
+/// Transitions happen at 02:00 local time unless the string says otherwise.
+const DEFAULT_TIME: i32 = 2 * 60 * 60;
+
+/// Parses a POSIX TZ string into the rule that it describes.
+///
+/// Returns `None` if the string is not valid.
+pub(crate) fn parse(tz_string: &str) -> Option<ExtraRule<'_>> {
+    let mut parser = Parser(tz_string.as_bytes());
+    let rule = parser.rule()?;
+    match parser.0.is_empty() {
+        true => Some(rule),
+        false => None,
+    }
+}
+
+struct Parser<'a>(&'a [u8]);
+
+impl<'a> Parser<'a> {
+    fn rule(&mut self) -> Option<ExtraRule<'a>> {
+        let standard_name = self.name()?;
+        let standard_offset = self.offset()?;
+        let standard = FixedTimespan {
+            utc_offset: standard_offset,
+            dst_offset: 0,
+            name: Some(standard_name),
+        };
+        if self.0.is_empty() {
+            return Some(ExtraRule {
+                standard,
+                daylight: None,
+            });
+        }
+
+        let daylight_name = self.name()?;
+        let daylight_offset = match self.0.first() {
+            Some(b',') | None => standard_offset + 60 * 60,
+            Some(_) => self.offset()?,
+        };
+        // Without a rule, POSIX leaves the dates up to the implementation; like the
+        // reference implementation we use the current rules of the United States.
+        let (start, end) = match self.eat(b',') {
+            true => {
+                let start = self.transition()?;
+                self.expect(b',')?;
+                (start, self.transition()?)
+            }
+            false => (
+                RuleTransition {
+                    date: RuleDate::MonthWeekday {
+                        month: 3,
+                        week: 2,
+                        weekday: 0,
+                    },
+                    time: DEFAULT_TIME,
+                },
+                RuleTransition {
+                    date: RuleDate::MonthWeekday {
+                        month: 11,
+                        week: 1,
+                        weekday: 0,
+                    },
+                    time: DEFAULT_TIME,
+                },
+            ),
+        };
+
+        Some(ExtraRule {
+            standard,
+            daylight: Some(DaylightRule {
+                timespan: FixedTimespan {
+                    utc_offset: standard_offset,
+                    dst_offset: daylight_offset - standard_offset,
+                    name: Some(daylight_name),
+                },
+                start,
+                end,
+            }),
+        })
+    }
+
+    /// An abbreviation of at least three letters, or of at least three letters, digits
+    /// and signs between angle brackets.
+    fn name(&mut self) -> Option<&'a str> {
+        let name = match self.eat(b'<') {
+            true => {
+                let name = self.take_while(|c| c.is_ascii_alphanumeric() || c == b'+' || c == b'-');
+                self.expect(b'>')?;
+                name
+            }
+            false => self.take_while(|c| c.is_ascii_alphabetic()),
+        };
+        match name.len() >= 3 {
+            true => core::str::from_utf8(name).ok(),
+            false => None,
+        }
+    }
+
+    /// The offset from UTC in seconds; POSIX counts hours west of Greenwich, so the
+    /// sign is flipped.
+    fn offset(&mut self) -> Option<i32> {
+        let offset = -self.signed_time(24)?;
+        // chrono's `FixedOffset` needs to be less than a day.
+        match offset.abs() < 24 * 60 * 60 {
+            true => Some(offset),
+            false => None,
+        }
+    }
+
+    fn transition(&mut self) -> Option<RuleTransition> {
+        let date = match self.0.first()? {
+            b'J' => {
+                self.0 = &self.0[1..];
+                RuleDate::JulianDay(self.number(1, 365)? as u16)
+            }
+            b'M' => {
+                self.0 = &self.0[1..];
+                let month = self.number(1, 12)? as u8;
+                self.expect(b'.')?;
+                let week = self.number(1, 5)? as u8;
+                self.expect(b'.')?;
+                let weekday = self.number(0, 6)? as u8;
+                RuleDate::MonthWeekday {
+                    month,
+                    week,
+                    weekday,
+                }
+            }
+            _ => RuleDate::DayOfYear(self.number(0, 365)? as u16),
+        };
+        let time = match self.eat(b'/') {
+            true => self.signed_time(167)?,
+            false => DEFAULT_TIME,
+        };
+        Some(RuleTransition { date, time })
+    }
+
+    /// `[+|-]hh[:mm[:ss]]` in seconds, with at most `max_hours` hours.
+    fn signed_time(&mut self, max_hours: u32) -> Option<i32> {
+        let sign = match self.0.first() {
+            Some(b'-') => -1,
+            _ => 1,
+        };
+        if let Some(b'+' | b'-') = self.0.first() {
+            self.0 = &self.0[1..];
+        }
+        let mut seconds = self.number(0, max_hours)? * 60 * 60;
+        if self.eat(b':') {
+            seconds += self.number(0, 59)? * 60;
+            if self.eat(b':') {
+                seconds += self.number(0, 59)?;
+            }
+        }
+        Some(sign * seconds as i32)
+    }
+
+    fn number(&mut self, min: u32, max: u32) -> Option<u32> {
+        let digits = self.take_while(|c| c.is_ascii_digit());
+        if digits.is_empty() || digits.len() > 3 {
+            return None;
+        }
+        let number = digits
+            .iter()
+            .fold(0, |number, digit| number * 10 + (digit - b'0') as u32);
+        match (min..=max).contains(&number) {
+            true => Some(number),
+            false => None,
+        }
+    }
+
+    fn take_while(&mut self, predicate: impl Fn(u8) -> bool) -> &'a [u8] {
+        let len = self.0.iter().take_while(|&&c| predicate(c)).count();
+        let (taken, rest) = self.0.split_at(len);
+        self.0 = rest;
+        taken
+    }
+
+    fn eat(&mut self, c: u8) -> bool {
+        match self.0.first() {
+            Some(&first) if first == c => {
+                self.0 = &self.0[1..];
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn expect(&mut self, c: u8) -> Option<()> {
+        self.eat(c).then_some(())
+    }
+}
@@ -5,8 +5,18 @@
     non_upper_case_globals
 )]
 
-#[cfg(not(any(feature = "case-insensitive", feature = "filter-by-regex")))]
+#[cfg(not(any(
+    feature = "case-insensitive",
+    feature = "filter-by-regex",
+    feature = "filter-by-list",
+    feature = "filter-by-years"
+)))]
 pub(crate) mod directory;
-#[cfg(not(any(feature = "case-insensitive", feature = "filter-by-regex")))]
+#[cfg(not(any(
+    feature = "case-insensitive",
+    feature = "filter-by-regex",
+    feature = "filter-by-list",
+    feature = "filter-by-years"
+)))]
 #[rustfmt::skip]
 pub(crate) mod timezones;
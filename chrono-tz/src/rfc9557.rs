@@ -0,0 +1,377 @@
+//! Timestamps with a time zone annotation, in the format of RFC 9557, such as
+//! `2025-03-30T03:30:00+02:00[Europe/Paris]`
+//!
+//! RFC 9557 extends the timestamps of RFC 3339 with suffixes in brackets. The first
+//! one may name a time zone; the others are `key=value` tags. A suffix that starts
+//! with `!` is critical: it must not be ignored. This is the format that the Temporal
+//! proposal for JavaScript reads and writes.
+
+use core::fmt::{self, Display, Formatter};
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, Offset, TimeZone, Timelike};
+
+use crate::timezone_impl::Disambiguation;
+use crate::timezones::Tz;
+
+/// What to do with a timestamp whose offset doesn't match the rules of its time zone,
+/// as in `2025-07-01T12:00:00+01:00[Europe/Paris]`.
+///
+/// This usually happens when the rules of the zone changed after the timestamp was
+/// written. These are the `offset` options of Temporal, which rejects by default.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum OffsetConflict {
+    /// Keep the instant that the offset gives, so that the local time changes.
+    UseOffset,
+    /// Keep the local time, and find its offset with the rules of the zone.
+    UseZone,
+    /// Return [`Rfc9557Error::OffsetConflict`].
+    #[default]
+    Reject,
+}
+
+/// The error returned by [`parse_rfc9557`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Rfc9557Error {
+    /// The input is not an RFC 9557 timestamp, or names a date that doesn't exist.
+    Invalid,
+    /// The input has no time zone annotation.
+    MissingTimeZone,
+    /// The time zone annotation names a zone that is not known, or an offset.
+    UnknownTimeZone,
+    /// The input has a critical tag that is not supported, such as `[!u-ca=hebrew]`.
+    UnsupportedCriticalTag,
+    /// The offset doesn't match the time zone, and either the policy or the critical
+    /// flag of the time zone says to reject it.
+    OffsetConflict,
+}
+
+impl Display for Rfc9557Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Rfc9557Error::Invalid => "invalid RFC 9557 timestamp",
+            Rfc9557Error::MissingTimeZone => "timestamp has no time zone annotation",
+            Rfc9557Error::UnknownTimeZone => "unknown time zone in timestamp",
+            Rfc9557Error::UnsupportedCriticalTag => "unsupported critical tag in timestamp",
+            Rfc9557Error::OffsetConflict => "offset of timestamp does not match its time zone",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Rfc9557Error {}
+
+/// Parses an RFC 9557 timestamp, such as `2025-03-30T03:30:00+02:00[Europe/Paris]`,
+/// whose time zone annotation names a zone of the tz database.
+///
+/// A `Z` instead of an offset means that only the instant is known, which is then
+/// shown in the zone. Otherwise `conflict` decides what happens when the offset doesn't
+/// match the zone, except that a conflict with a critical zone, as in `[!Europe/Paris]`,
+/// is always an error. Tags are ignored unless they are critical, which is an error
+/// for all but the ISO 8601 calendar `u-ca=iso8601`.
+///
+/// ```
+/// use chrono::TimeZone;
+/// use chrono_tz::{parse_rfc9557, OffsetConflict, Rfc9557Error, Tz};
+///
+/// let dt = parse_rfc9557("2025-03-30T03:30:00+02:00[Europe/Paris]", OffsetConflict::Reject);
+/// assert_eq!(dt, Ok(Tz::Europe__Paris.with_ymd_and_hms(2025, 3, 30, 3, 30, 0).unwrap()));
+///
+/// // Paris is an hour ahead of UTC in winter.
+/// let input = "2025-01-15T12:00:00+02:00[Europe/Paris]";
+/// let dt = parse_rfc9557(input, OffsetConflict::UseOffset).unwrap();
+/// assert_eq!(dt.to_rfc3339(), "2025-01-15T11:00:00+01:00");
+/// let dt = parse_rfc9557(input, OffsetConflict::UseZone).unwrap();
+/// assert_eq!(dt.to_rfc3339(), "2025-01-15T12:00:00+01:00");
+/// assert_eq!(parse_rfc9557(input, OffsetConflict::Reject), Err(Rfc9557Error::OffsetConflict));
+/// ```
+pub fn parse_rfc9557(input: &str, conflict: OffsetConflict) -> Result<DateTime<Tz>, Rfc9557Error> {
+    parse_rfc9557_with(input, conflict, |name| name.parse().ok())
+}
+
+/// Like [`parse_rfc9557`], with `find_tz` to look up the name of the zone.
+pub(crate) fn parse_rfc9557_with(
+    input: &str,
+    conflict: OffsetConflict,
+    find_tz: impl FnOnce(&str) -> Option<Tz>,
+) -> Result<DateTime<Tz>, Rfc9557Error> {
+    let mut parser = Parser(input.as_bytes());
+    let local = parser.date_time().ok_or(Rfc9557Error::Invalid)?;
+    let (offset, has_seconds) = match parser.utc_offset().ok_or(Rfc9557Error::Invalid)? {
+        Some((offset, has_seconds)) => (Some(offset), has_seconds),
+        None => (None, false),
+    };
+
+    let mut zone = None;
+    let mut first = true;
+    while parser.eat(b'[') {
+        let critical = parser.eat(b'!');
+        let suffix = parser.take_while(|c| c != b']');
+        parser.expect(b']').ok_or(Rfc9557Error::Invalid)?;
+        let suffix = core::str::from_utf8(suffix).map_err(|_| Rfc9557Error::Invalid)?;
+        match suffix.split_once('=') {
+            Some((key, value)) => {
+                if !is_key(key) || !is_value(value) {
+                    return Err(Rfc9557Error::Invalid);
+                }
+                if critical && !(key == "u-ca" && value == "iso8601") {
+                    return Err(Rfc9557Error::UnsupportedCriticalTag);
+                }
+            }
+            // Only the first suffix may be a time zone.
+            None if first => zone = Some((suffix, critical)),
+            None => return Err(Rfc9557Error::Invalid),
+        }
+        first = false;
+    }
+    if !parser.0.is_empty() {
+        return Err(Rfc9557Error::Invalid);
+    }
+
+    let (name, critical) = zone.ok_or(Rfc9557Error::MissingTimeZone)?;
+    let tz = find_tz(name).ok_or(Rfc9557Error::UnknownTimeZone)?;
+    let offset = match offset {
+        Some(offset) => offset,
+        None => return Ok(tz.from_utc_datetime(&local)),
+    };
+
+    let utc = local
+        .checked_sub_signed(Duration::seconds(offset.into()))
+        .ok_or(Rfc9557Error::Invalid)?;
+    let dt = tz.from_utc_datetime(&utc);
+    if dt.offset().fix().local_minus_utc() == offset {
+        return Ok(dt);
+    }
+    // Offsets are written rounded to minutes, so an offset without seconds also
+    // matches the offset with seconds of local mean time that it rounds.
+    if !has_seconds && (conflict != OffsetConflict::UseOffset || critical) {
+        let local = tz.from_local_datetime(&local);
+        let matching = [local.earliest(), local.latest()]
+            .into_iter()
+            .flatten()
+            .find(|dt| round_to_minutes(dt.offset().fix().local_minus_utc()) == offset);
+        if let Some(dt) = matching {
+            return Ok(dt);
+        }
+    }
+    match (conflict, critical) {
+        (OffsetConflict::Reject, _) | (_, true) => Err(Rfc9557Error::OffsetConflict),
+        (OffsetConflict::UseOffset, false) => Ok(dt),
+        (OffsetConflict::UseZone, false) => tz
+            .resolve_local(&local, Disambiguation::Compatible)
+            .map_err(|_| Rfc9557Error::OffsetConflict),
+    }
+}
+
+/// Formats `dt` as an RFC 3339 timestamp followed by the name of its zone, as in
+/// `2025-03-30T03:30:00+02:00[Europe/Paris]`.
+///
+/// Fractions of a second are written in groups of three digits, and only if there
+/// are any. As RFC 3339 has no seconds in offsets, the offsets of local mean time are
+/// rounded to minutes, as Temporal does; [`parse_rfc9557`] reads them back.
+///
+/// ```
+/// use chrono::TimeZone;
+/// use chrono_tz::{format_rfc9557, Tz};
+///
+/// let dt = Tz::Europe__Paris.with_ymd_and_hms(2025, 3, 30, 3, 30, 0).unwrap();
+/// assert_eq!(format_rfc9557(&dt).to_string(), "2025-03-30T03:30:00+02:00[Europe/Paris]");
+/// ```
+pub fn format_rfc9557(dt: &DateTime<Tz>) -> impl Display + '_ {
+    Rfc9557(dt)
+}
+
+struct Rfc9557<'a>(&'a DateTime<Tz>);
+
+impl Display for Rfc9557<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write_date_time(f, &self.0.naive_local())?;
+        let offset = round_to_minutes(self.0.offset().fix().local_minus_utc());
+        let sign = if offset < 0 { '-' } else { '+' };
+        let offset = offset.abs();
+        write!(f, "{sign}{:02}:{:02}", offset / 3600, offset / 60 % 60)?;
+        write!(f, "[{}]", self.0.timezone().name())
+    }
+}
+
+/// `offset` rounded to the nearest minute, with half a minute rounded away from zero.
+fn round_to_minutes(offset: i32) -> i32 {
+    offset.signum() * ((offset.abs() + 30) / 60 * 60)
+}
+
+/// An instant as an RFC 3339 timestamp in UTC, such as `2025-03-30T01:30:00Z`.
+#[cfg(feature = "serde")]
+pub(crate) struct Rfc3339Utc(pub(crate) NaiveDateTime);
+
+#[cfg(feature = "serde")]
+impl Display for Rfc3339Utc {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write_date_time(f, &self.0)?;
+        f.write_str("Z")
+    }
+}
+
+/// Parses an RFC 3339 timestamp into the UTC time of its instant.
+#[cfg(feature = "serde")]
+pub(crate) fn parse_rfc3339(input: &str) -> Option<NaiveDateTime> {
+    let mut parser = Parser(input.as_bytes());
+    let local = parser.date_time()?;
+    let offset = parser.utc_offset()?.map_or(0, |(offset, _)| offset);
+    if !parser.0.is_empty() {
+        return None;
+    }
+    local.checked_sub_signed(Duration::seconds(offset.into()))
+}
+
+/// Writes `local` as `yyyy-mm-ddThh:mm:ss[.fraction]`.
+fn write_date_time(f: &mut Formatter<'_>, local: &NaiveDateTime) -> fmt::Result {
+    match local.year() {
+        year @ 0..=9999 => write!(f, "{year:04}")?,
+        year => write!(f, "{year:+07}")?,
+    }
+    // chrono stores a leap second as a nanosecond count past the 59th second.
+    let (second, nanosecond) = match local.nanosecond() {
+        nanosecond @ 1_000_000_000.. => (60, nanosecond - 1_000_000_000),
+        nanosecond => (local.second(), nanosecond),
+    };
+    write!(
+        f,
+        "-{:02}-{:02}T{:02}:{:02}:{second:02}",
+        local.month(),
+        local.day(),
+        local.hour(),
+        local.minute()
+    )?;
+    match nanosecond {
+        0 => {}
+        _ if nanosecond % 1_000_000 == 0 => write!(f, ".{:03}", nanosecond / 1_000_000)?,
+        _ if nanosecond % 1_000 == 0 => write!(f, ".{:06}", nanosecond / 1_000)?,
+        _ => write!(f, ".{nanosecond:09}")?,
+    }
+    Ok(())
+}
+
+/// Tag keys are lowercase, like `u-ca`.
+fn is_key(key: &str) -> bool {
+    let mut bytes = key.bytes();
+    matches!(bytes.next(), Some(b'a'..=b'z' | b'_'))
+        && bytes.all(|c| matches!(c, b'a'..=b'z' | b'0'..=b'9' | b'_' | b'-'))
+}
+
+/// Tag values are alphanumeric parts separated by `-`, like `islamic-civil`.
+fn is_value(value: &str) -> bool {
+    value
+        .split('-')
+        .all(|part| !part.is_empty() && part.bytes().all(|c| c.is_ascii_alphanumeric()))
+}
+
+struct Parser<'a>(&'a [u8]);
+
+impl<'a> Parser<'a> {
+    /// `yyyy-mm-ddThh:mm:ss[.fraction]`, where the year may also have a sign and six
+    /// digits. A leap second is read as the second before it, as Temporal does.
+    fn date_time(&mut self) -> Option<NaiveDateTime> {
+        let year = match self.0.first()? {
+            &sign @ (b'+' | b'-') => {
+                self.0 = &self.0[1..];
+                let year = self.digits(6)? as i32;
+                match sign {
+                    // The year zero is written with a plus sign.
+                    b'-' if year == 0 => return None,
+                    b'-' => -year,
+                    _ => year,
+                }
+            }
+            _ => self.digits(4)? as i32,
+        };
+        self.expect(b'-')?;
+        let month = self.digits(2)?;
+        self.expect(b'-')?;
+        let day = self.digits(2)?;
+        let date = NaiveDate::from_ymd_opt(year, month, day)?;
+
+        match self.0.first()? {
+            b'T' | b't' | b' ' => self.0 = &self.0[1..],
+            _ => return None,
+        }
+        let hour = self.digits(2)?;
+        self.expect(b':')?;
+        let minute = self.digits(2)?;
+        self.expect(b':')?;
+        let second = match self.digits(2)? {
+            60 => 59,
+            second => second,
+        };
+        let mut nanosecond = 0;
+        if self.eat(b'.') || self.eat(b',') {
+            let digits = self.take_while(|c| c.is_ascii_digit());
+            if digits.is_empty() || digits.len() > 9 {
+                return None;
+            }
+            nanosecond = digits
+                .iter()
+                .fold(0, |number, digit| number * 10 + (digit - b'0') as u32)
+                * 10u32.pow(9 - digits.len() as u32);
+        }
+        date.and_hms_nano_opt(hour, minute, second, nanosecond)
+    }
+
+    /// `Z`, which leaves the offset unknown, or `+hh:mm[:ss]` in seconds, with whether
+    /// it has seconds.
+    fn utc_offset(&mut self) -> Option<Option<(i32, bool)>> {
+        if self.eat(b'Z') || self.eat(b'z') {
+            return Some(None);
+        }
+        let sign = match self.0.first()? {
+            b'+' => 1,
+            b'-' => -1,
+            _ => return None,
+        };
+        self.0 = &self.0[1..];
+        let mut seconds = self.digits(2)? as i32 * 3600;
+        self.expect(b':')?;
+        seconds += self.digits(2)? as i32 * 60;
+        let has_seconds = self.eat(b':');
+        if has_seconds {
+            seconds += self.digits(2)? as i32;
+        }
+        match seconds < 24 * 3600 {
+            true => Some(Some((sign * seconds, has_seconds))),
+            false => None,
+        }
+    }
+
+    /// Exactly `len` digits.
+    fn digits(&mut self, len: usize) -> Option<u32> {
+        let digits = self.0.get(..len)?;
+        if !digits.iter().all(u8::is_ascii_digit) {
+            return None;
+        }
+        self.0 = &self.0[len..];
+        Some(
+            digits
+                .iter()
+                .fold(0, |number, digit| number * 10 + (digit - b'0') as u32),
+        )
+    }
+
+    fn take_while(&mut self, predicate: impl Fn(u8) -> bool) -> &'a [u8] {
+        let len = self.0.iter().take_while(|&&c| predicate(c)).count();
+        let (taken, rest) = self.0.split_at(len);
+        self.0 = rest;
+        taken
+    }
+
+    fn eat(&mut self, c: u8) -> bool {
+        match self.0.first() {
+            Some(&first) if first == c => {
+                self.0 = &self.0[1..];
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn expect(&mut self, c: u8) -> Option<()> {
+        self.eat(c).then_some(())
+    }
+}
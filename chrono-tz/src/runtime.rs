@@ -0,0 +1,281 @@
+//! Time zone databases loaded at runtime from tzdb bundles.
+//!
+//! A bundle is a single file with the compiled zones of a release of the tz database,
+//! its links and version, and optionally its country tables. `chrono-tz-build` writes
+//! them with `write_bundle`. Unlike [`Tz`](crate::Tz), whose zones are compiled in, a
+//! [`Database`] can be loaded from a new bundle while the program runs, and a
+//! [`SharedDatabase`] swaps it in for every thread at once.
+//!
+//! ```no_run
+//! use chrono::TimeZone;
+//! use chrono_tz::runtime::Database;
+//!
+//! let database = Database::from_bytes(&std::fs::read("tzdb.bin").unwrap()).unwrap();
+//! let london = database.get("Europe/London").unwrap();
+//! let dt = london.with_ymd_and_hms(2016, 5, 10, 12, 0, 0).unwrap();
+//! assert_eq!(dt.to_string(), "2016-05-10 12:00:00 BST");
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Debug, Display, Formatter};
+use std::sync::{Arc, RwLock};
+
+use crate::tzif::{DynTz, TzifError};
+
+/// The format version of the bundles that can be read.
+const FORMAT_VERSION: u8 = 1;
+
+/// The zones, links and countries of a tzdb bundle.
+pub struct Database {
+    version: Box<str>,
+    zones: BTreeMap<Box<str>, DynTz>,
+    links: BTreeMap<Box<str>, Box<str>>,
+    countries: Vec<Country>,
+}
+
+/// A country, with the zones that the bundle lists for it.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Country {
+    /// The ISO 3166 alpha-2 code of the country, such as `DE`.
+    pub code: String,
+    /// The name of the country in English, such as `Germany`.
+    pub name: String,
+    /// The names of the zones of the country, with the most populous areas first.
+    pub zones: Vec<String>,
+}
+
+impl Database {
+    /// Parses a tzdb bundle, along with the TZif data of all of its zones.
+    pub fn from_bytes(data: &[u8]) -> Result<Self, BundleError> {
+        let mut reader = Reader(data);
+        if reader.take(4)? != b"TZDB" {
+            return Err(BundleError::InvalidData("not a tzdb bundle"));
+        }
+        if reader.take(1)? != [FORMAT_VERSION] {
+            return Err(BundleError::InvalidData("unknown format version"));
+        }
+        let version = reader.str()?.into();
+
+        let mut zones = BTreeMap::new();
+        for _ in 0..reader.u32()? {
+            let name = reader.str()?;
+            let len = reader.u32()? as usize;
+            let tzif = reader.take(len)?;
+            zones.insert(name.into(), parse_zone(name, tzif)?);
+        }
+
+        // Links share the data of their target under their own name, like `Tz` does.
+        let mut links = BTreeMap::new();
+        for _ in 0..reader.u32()? {
+            let name = reader.str()?;
+            let target = reader.str()?;
+            let zone = zones
+                .get(target)
+                .ok_or(BundleError::InvalidData("link to an unknown zone"))?
+                .with_name(name);
+            zones.insert(name.into(), zone);
+            links.insert(name.into(), target.into());
+        }
+
+        let mut countries = Vec::new();
+        for _ in 0..reader.u32()? {
+            let code = reader.str()?.to_owned();
+            let name = reader.str()?.to_owned();
+            let mut country_zones = Vec::new();
+            for _ in 0..reader.u16()? {
+                let zone = reader.str()?;
+                if !zones.contains_key(zone) {
+                    return Err(BundleError::InvalidData("country with an unknown zone"));
+                }
+                country_zones.push(zone.to_owned());
+            }
+            countries.push(Country {
+                code,
+                name,
+                zones: country_zones,
+            });
+        }
+        countries.sort_by(|a, b| a.code.cmp(&b.code));
+
+        if !reader.0.is_empty() {
+            return Err(BundleError::InvalidData("trailing data"));
+        }
+        Ok(Database {
+            version,
+            zones,
+            links,
+            countries,
+        })
+    }
+
+    /// The version of the tz database, such as `2024b`.
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// Looks up a zone or link by its name, such as `Europe/London`.
+    ///
+    /// The zone stays valid after the database is dropped or replaced.
+    pub fn get(&self, name: &str) -> Option<DynTz> {
+        self.zones.get(name).cloned()
+    }
+
+    /// The names of all zones and links, sorted.
+    pub fn names(&self) -> impl Iterator<Item = &str> + '_ {
+        self.zones.keys().map(|name| &**name)
+    }
+
+    /// The name of the zone that `name` is a link to, or `name` itself if it is a zone.
+    ///
+    /// Returns `None` for unknown names.
+    pub fn canonical<'a>(&'a self, name: &'a str) -> Option<&'a str> {
+        match self.links.get(name) {
+            Some(target) => Some(target),
+            None => self.zones.contains_key(name).then_some(name),
+        }
+    }
+
+    /// Looks up a country by its ISO 3166 alpha-2 code, such as `DE`.
+    ///
+    /// Bundles written without country tables have no countries.
+    pub fn country(&self, code: &str) -> Option<&Country> {
+        self.countries
+            .binary_search_by(|country| country.code.as_str().cmp(code))
+            .ok()
+            .map(|index| &self.countries[index])
+    }
+
+    /// Every country of the bundle, sorted by code.
+    pub fn countries(&self) -> &[Country] {
+        &self.countries
+    }
+}
+
+impl Debug for Database {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Database")
+            .field("version", &self.version)
+            .field("zones", &self.zones.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// A [`Database`] that can be replaced while other threads use it.
+///
+/// Each lookup sees either the old or the new database as a whole. Zones that were
+/// handed out before keep the rules of the database they came from.
+///
+/// ```no_run
+/// use chrono_tz::runtime::{Database, SharedDatabase};
+///
+/// let load = || Database::from_bytes(&std::fs::read("tzdb.bin").unwrap()).unwrap();
+/// let shared = SharedDatabase::new(load());
+/// // Later, after a new bundle was installed:
+/// let old = shared.replace(load());
+/// println!("updated from {} to {}", old.version(), shared.current().version());
+/// ```
+pub struct SharedDatabase(RwLock<Arc<Database>>);
+
+impl SharedDatabase {
+    /// Shares `database`.
+    pub fn new(database: Database) -> Self {
+        SharedDatabase(RwLock::new(Arc::new(database)))
+    }
+
+    /// The database in use right now.
+    pub fn current(&self) -> Arc<Database> {
+        let current = self
+            .0
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        Arc::clone(&current)
+    }
+
+    /// Looks up a zone or link in the current database.
+    pub fn get(&self, name: &str) -> Option<DynTz> {
+        self.current().get(name)
+    }
+
+    /// Replaces the database for all later lookups, and returns the previous one.
+    pub fn replace(&self, database: Database) -> Arc<Database> {
+        let mut current = self
+            .0
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        std::mem::replace(&mut current, Arc::new(database))
+    }
+}
+
+impl Debug for SharedDatabase {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SharedDatabase")
+            .field(&self.current())
+            .finish()
+    }
+}
+
+/// An error while loading a [`Database`].
+#[derive(Debug)]
+pub enum BundleError {
+    /// The data is not a valid tzdb bundle.
+    InvalidData(&'static str),
+    /// The TZif data of a zone is not valid.
+    InvalidZone {
+        /// The name of the zone.
+        name: String,
+        /// What is wrong with its data.
+        error: TzifError,
+    },
+}
+
+impl Display for BundleError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            BundleError::InvalidData(reason) => write!(f, "invalid tzdb bundle: {reason}"),
+            BundleError::InvalidZone { name, error } => write!(f, "invalid zone {name}: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for BundleError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BundleError::InvalidZone { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+fn parse_zone(name: &str, tzif: &[u8]) -> Result<DynTz, BundleError> {
+    DynTz::from_tzif(name, tzif).map_err(|error| BundleError::InvalidZone {
+        name: name.to_owned(),
+        error,
+    })
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], BundleError> {
+        if self.0.len() < len {
+            return Err(BundleError::InvalidData("unexpected end of data"));
+        }
+        let (taken, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(taken)
+    }
+
+    fn u16(&mut self) -> Result<u16, BundleError> {
+        Ok(u16::from_be_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn u32(&mut self) -> Result<u32, BundleError> {
+        Ok(u32::from_be_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn str(&mut self) -> Result<&'a str, BundleError> {
+        let len = self.take(1)?[0] as usize;
+        std::str::from_utf8(self.take(len)?)
+            .map_err(|_| BundleError::InvalidData("name is not UTF-8"))
+    }
+}
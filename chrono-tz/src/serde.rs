@@ -1,8 +1,50 @@
+//! Serialization of [`Tz`], and helpers for the fields of type `DateTime<Tz>`
+//!
+//! A `Tz` is serialized as its name. chrono can't serialize a `DateTime<Tz>` on its own,
+//! since the offset alone doesn't say which zone it was in. The modules [`zoned`] and
+//! [`utc_and_tz`] serialize it along with the name of the zone, for use with
+//! `#[serde(with = "...")]`:
+//!
+//! ```
+//! use chrono::{DateTime, TimeZone};
+//! use chrono_tz::Tz;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Debug, PartialEq, Serialize, Deserialize)]
+//! struct Meeting {
+//!     #[serde(with = "chrono_tz::serde::zoned")]
+//!     start: DateTime<Tz>,
+//!     #[serde(with = "chrono_tz::serde::utc_and_tz")]
+//!     end: DateTime<Tz>,
+//! }
+//!
+//! let meeting = Meeting {
+//!     start: Tz::Europe__London.with_ymd_and_hms(2025, 10, 26, 1, 30, 0).earliest().unwrap(),
+//!     end: Tz::Europe__London.with_ymd_and_hms(2025, 10, 26, 1, 30, 0).latest().unwrap(),
+//! };
+//! let json = serde_json::to_string(&meeting).unwrap();
+//! assert_eq!(
+//!     json,
+//!     r#"{"start":"2025-10-26T01:30:00+01:00[Europe/London]","#.to_owned()
+//!         + r#""end":{"utc":"2025-10-26T01:30:00Z","tz":"Europe/London"}}"#
+//! );
+//! assert_eq!(serde_json::from_str::<Meeting>(&json).unwrap(), meeting);
+//! ```
+//!
+//! Both keep the instant along with the zone, so that a local time which happens twice,
+//! like 01:30 in London on the day that summer time ends, comes back as the same one.
+//!
+//! With the `case-insensitive` feature, names of zones are deserialized regardless of
+//! case, so that `europe/london` is read as `Europe/London`.
+
 extern crate serde;
 
 use self::serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use core::fmt;
 
+use chrono::{DateTime, NaiveDateTime};
+
+use crate::rfc9557::{self, Rfc3339Utc};
 use crate::timezones::Tz;
 
 impl Serialize for Tz {
@@ -23,9 +65,196 @@ impl<'de> Deserialize<'de> for Tz {
             }
 
             fn visit_str<E: de::Error>(self, value: &str) -> Result<Tz, E> {
-                value
-                    .parse::<Tz>()
-                    .map_err(|_| E::custom(SerdeError(value)))
+                find_tz(value).ok_or_else(|| E::custom(SerdeError(value)))
+            }
+        }
+
+        deserializer.deserialize_str(Visitor)
+    }
+}
+
+#[cfg(not(feature = "case-insensitive"))]
+fn find_tz(name: &str) -> Option<Tz> {
+    name.parse().ok()
+}
+
+#[cfg(feature = "case-insensitive")]
+fn find_tz(name: &str) -> Option<Tz> {
+    Tz::from_str_insensitive(name).ok()
+}
+
+/// Serializes a `DateTime<Tz>` as an RFC 9557 string, such as
+/// `2025-10-26T01:30:00+01:00[Europe/London]`
+///
+/// The offset tells which of the two times is meant when a local time happens twice.
+/// Deserialization fails if the offset doesn't match the rules of the zone, as it does
+/// for [`OffsetConflict::Reject`](crate::OffsetConflict::Reject); it also accepts a `Z`
+/// instead of an offset.
+pub mod zoned {
+    use core::fmt;
+
+    use super::serde::{de, Deserializer, Serializer};
+    use chrono::DateTime;
+
+    use super::find_tz;
+    use crate::rfc9557::{format_rfc9557, parse_rfc9557_with, OffsetConflict};
+    use crate::timezones::Tz;
+
+    /// Serializes `dt` as an RFC 9557 string.
+    pub fn serialize<S: Serializer>(dt: &DateTime<Tz>, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&format_rfc9557(dt))
+    }
+
+    /// Deserializes a `DateTime<Tz>` from an RFC 9557 string.
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<DateTime<Tz>, D::Error> {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = DateTime<Tz>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "an RFC 9557 timestamp with a time zone name")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<DateTime<Tz>, E> {
+                parse_rfc9557_with(value, OffsetConflict::Reject, find_tz)
+                    .map_err(|err| E::custom(format_args!("{err}: '{value}'")))
+            }
+        }
+
+        deserializer.deserialize_str(Visitor)
+    }
+}
+
+/// Serializes a `DateTime<Tz>` as a struct of its instant and its zone, such as
+/// `{ "utc": "2025-10-26T01:30:00Z", "tz": "Europe/London" }`
+///
+/// The instant is an RFC 3339 string in UTC; deserialization accepts any offset.
+pub mod utc_and_tz {
+    use core::fmt;
+
+    use super::serde::de::{self, MapAccess, SeqAccess};
+    use super::serde::ser::SerializeStruct;
+    use super::serde::{Deserialize, Deserializer, Serializer};
+    use chrono::{DateTime, TimeZone};
+
+    use super::{Instant, UtcInstant};
+    use crate::timezones::Tz;
+
+    const FIELDS: &[&str] = &["utc", "tz"];
+
+    /// Serializes `dt` as a struct with the fields `utc` and `tz`.
+    pub fn serialize<S: Serializer>(dt: &DateTime<Tz>, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("DateTime", 2)?;
+        state.serialize_field("utc", &UtcInstant(dt))?;
+        state.serialize_field("tz", &dt.timezone())?;
+        state.end()
+    }
+
+    /// Deserializes a `DateTime<Tz>` from a struct with the fields `utc` and `tz`.
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<DateTime<Tz>, D::Error> {
+        enum Field {
+            Utc,
+            Tz,
+        }
+
+        impl<'de> Deserialize<'de> for Field {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                struct Visitor;
+
+                impl<'de> de::Visitor<'de> for Visitor {
+                    type Value = Field;
+
+                    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                        write!(formatter, "`utc` or `tz`")
+                    }
+
+                    fn visit_str<E: de::Error>(self, value: &str) -> Result<Field, E> {
+                        match value {
+                            "utc" => Ok(Field::Utc),
+                            "tz" => Ok(Field::Tz),
+                            _ => Err(E::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+
+                deserializer.deserialize_identifier(Visitor)
+            }
+        }
+
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = DateTime<Tz>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "a struct with an instant and a time zone")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<DateTime<Tz>, A::Error> {
+                let Instant(utc) = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let tz: Tz = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+                Ok(tz.from_utc_datetime(&utc))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<DateTime<Tz>, A::Error> {
+                let mut utc = None;
+                let mut tz = None;
+                while let Some(key) = map.next_key()? {
+                    match key {
+                        Field::Utc if utc.is_some() => {
+                            return Err(de::Error::duplicate_field("utc"))
+                        }
+                        Field::Utc => utc = Some(map.next_value::<Instant>()?.0),
+                        Field::Tz if tz.is_some() => return Err(de::Error::duplicate_field("tz")),
+                        Field::Tz => tz = Some(map.next_value::<Tz>()?),
+                    }
+                }
+                let utc = utc.ok_or_else(|| de::Error::missing_field("utc"))?;
+                let tz = tz.ok_or_else(|| de::Error::missing_field("tz"))?;
+                Ok(tz.from_utc_datetime(&utc))
+            }
+        }
+
+        deserializer.deserialize_struct("DateTime", FIELDS, Visitor)
+    }
+}
+
+/// Serializes the instant of a `DateTime` as an RFC 3339 string in UTC.
+struct UtcInstant<'a>(&'a DateTime<Tz>);
+
+impl Serialize for UtcInstant<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&Rfc3339Utc(self.0.naive_utc()))
+    }
+}
+
+/// Deserializes an RFC 3339 string into the UTC time of its instant.
+struct Instant(NaiveDateTime);
+
+impl<'de> Deserialize<'de> for Instant {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = Instant;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "an RFC 3339 timestamp")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Instant, E> {
+                rfc9557::parse_rfc3339(value)
+                    .map(Instant)
+                    .ok_or_else(|| E::custom(format_args!("invalid RFC 3339 timestamp: '{value}'")))
             }
         }
 
@@ -44,7 +273,30 @@ impl fmt::Display for SerdeError<'_> {
 #[cfg(test)]
 mod tests {
     use crate::timezones::Tz::{self, Etc__UTC, Europe__London, UTC};
-    use serde_test::{assert_de_tokens_error, assert_tokens, Token};
+    use chrono::{DateTime, TimeZone, Timelike};
+    use serde::{Deserialize, Serialize};
+    use serde_test::{assert_de_tokens, assert_de_tokens_error, assert_tokens, Token};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Zoned(#[serde(with = "super::zoned")] DateTime<Tz>);
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct UtcAndTz(#[serde(with = "super::utc_and_tz")] DateTime<Tz>);
+
+    fn utc_and_tz_tokens(utc: &'static str, tz: &'static str) -> [Token; 7] {
+        [
+            Token::NewtypeStruct { name: "UtcAndTz" },
+            Token::Struct {
+                name: "DateTime",
+                len: 2,
+            },
+            Token::Str("utc"),
+            Token::Str(utc),
+            Token::Str("tz"),
+            Token::Str(tz),
+            Token::StructEnd,
+        ]
+    }
 
     #[test]
     fn serde_ok_both_ways() {
@@ -60,4 +312,128 @@ mod tests {
             "failed to parse timezone: 'Europe/L'",
         );
     }
+
+    #[test]
+    fn zoned_ambiguous_times() {
+        let local = Europe__London.with_ymd_and_hms(2025, 10, 26, 1, 30, 0);
+        for (dt, string) in [
+            (local.earliest(), "2025-10-26T01:30:00+01:00[Europe/London]"),
+            (local.latest(), "2025-10-26T01:30:00+00:00[Europe/London]"),
+        ] {
+            assert_tokens(
+                &Zoned(dt.unwrap()),
+                &[Token::NewtypeStruct { name: "Zoned" }, Token::Str(string)],
+            );
+        }
+
+        let dt = Tz::America__Sao_Paulo
+            .with_ymd_and_hms(1913, 6, 1, 0, 0, 0)
+            .unwrap()
+            .with_nanosecond(123_456_000)
+            .unwrap();
+        assert_tokens(
+            &Zoned(dt),
+            &[
+                Token::NewtypeStruct { name: "Zoned" },
+                Token::Str("1913-06-01T00:00:00.123456-03:06[America/Sao_Paulo]"),
+            ],
+        );
+    }
+
+    #[test]
+    fn zoned_de() {
+        let dt = Europe__London.with_ymd_and_hms(2025, 10, 26, 1, 30, 0);
+        assert_de_tokens(
+            &Zoned(dt.latest().unwrap()),
+            &[
+                Token::NewtypeStruct { name: "Zoned" },
+                Token::Str("2025-10-26T01:30:00Z[Europe/London]"),
+            ],
+        );
+        assert_de_tokens_error::<Zoned>(
+            &[
+                Token::NewtypeStruct { name: "Zoned" },
+                Token::Str("2025-07-01T12:00:00+00:00[Europe/London]"),
+            ],
+            "offset of timestamp does not match its time zone: \
+             '2025-07-01T12:00:00+00:00[Europe/London]'",
+        );
+        assert_de_tokens_error::<Zoned>(
+            &[
+                Token::NewtypeStruct { name: "Zoned" },
+                Token::Str("2025-07-01T12:00:00+01:00"),
+            ],
+            "timestamp has no time zone annotation: '2025-07-01T12:00:00+01:00'",
+        );
+    }
+
+    #[test]
+    fn utc_and_tz_ambiguous_times() {
+        let local = Europe__London.with_ymd_and_hms(2025, 10, 26, 1, 30, 0);
+        for (dt, utc) in [
+            (local.earliest(), "2025-10-26T00:30:00Z"),
+            (local.latest(), "2025-10-26T01:30:00Z"),
+        ] {
+            let tokens = utc_and_tz_tokens(utc, "Europe/London");
+            assert_tokens(&UtcAndTz(dt.unwrap()), &tokens);
+        }
+    }
+
+    #[test]
+    fn utc_and_tz_de() {
+        let dt = Europe__London.with_ymd_and_hms(2025, 10, 26, 1, 30, 0);
+        let tokens = utc_and_tz_tokens("2025-10-26T01:30:00+01:00", "Europe/London");
+        assert_de_tokens(&UtcAndTz(dt.earliest().unwrap()), &tokens);
+
+        assert_de_tokens(
+            &UtcAndTz(dt.latest().unwrap()),
+            &[
+                Token::NewtypeStruct { name: "UtcAndTz" },
+                Token::Seq { len: Some(2) },
+                Token::Str("2025-10-26T01:30:00Z"),
+                Token::Str("Europe/London"),
+                Token::SeqEnd,
+            ],
+        );
+
+        let tokens = utc_and_tz_tokens("2025-10-26 01:30", "Europe/London");
+        assert_de_tokens_error::<UtcAndTz>(
+            &tokens[..4],
+            "invalid RFC 3339 timestamp: '2025-10-26 01:30'",
+        );
+        let tokens = utc_and_tz_tokens("2025-10-26T01:30:00Z", "Europe/L");
+        assert_de_tokens_error::<UtcAndTz>(&tokens[..6], "failed to parse timezone: 'Europe/L'");
+        assert_de_tokens_error::<UtcAndTz>(
+            &[
+                Token::NewtypeStruct { name: "UtcAndTz" },
+                Token::Struct {
+                    name: "DateTime",
+                    len: 1,
+                },
+                Token::Str("utc"),
+                Token::Str("2025-10-26T01:30:00Z"),
+                Token::StructEnd,
+            ],
+            "missing field `tz`",
+        );
+    }
+
+    #[cfg(feature = "case-insensitive")]
+    #[test]
+    fn case_insensitive_names() {
+        assert_de_tokens(&Europe__London, &[Token::Str("europe/london")]);
+
+        let dt = Europe__London
+            .with_ymd_and_hms(2025, 7, 1, 12, 0, 0)
+            .unwrap();
+        assert_de_tokens(
+            &Zoned(dt),
+            &[
+                Token::NewtypeStruct { name: "Zoned" },
+                Token::Str("2025-07-01T12:00:00+01:00[EUROPE/LONDON]"),
+            ],
+        );
+        let tokens = utc_and_tz_tokens("2025-07-01T11:00:00Z", "europe/LONDON");
+        assert_de_tokens(&UtcAndTz(dt), &tokens);
+    }
 }
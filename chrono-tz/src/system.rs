@@ -0,0 +1,215 @@
+//! Guessing the time zone that the system is set to, from the places where Linux
+//! distributions keep it.
+
+use std::cmp::Reverse;
+use std::env;
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use chrono::{TimeZone, Utc};
+
+use crate::timezone_impl::{Equivalence, TimeSpans};
+use crate::timezones::{Tz, TZ_VARIANTS};
+use crate::tzif::DynTz;
+
+/// Where [`system_tz_with()`] looks for the time zone of the system.
+///
+/// [`SystemTzConfig::new()`] gives the places of the running system. Tests can point
+/// the paths at a directory of their own instead.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SystemTzConfig {
+    /// The value of the `TZ` environment variable, if it is set.
+    pub tz_var: Option<String>,
+    /// The compiled zone of the system, or a link to it: usually `/etc/localtime`.
+    pub localtime: PathBuf,
+    /// The file that names the zone of the system on Debian and its derivatives:
+    /// usually `/etc/timezone`.
+    pub timezone_file: PathBuf,
+    /// The directory of compiled zones: usually `/usr/share/zoneinfo`.
+    pub zoneinfo: PathBuf,
+}
+
+impl SystemTzConfig {
+    /// The `TZ` environment variable of this process and the usual paths.
+    pub fn new() -> Self {
+        SystemTzConfig {
+            tz_var: env::var("TZ").ok(),
+            localtime: PathBuf::from("/etc/localtime"),
+            timezone_file: PathBuf::from("/etc/timezone"),
+            zoneinfo: PathBuf::from("/usr/share/zoneinfo"),
+        }
+    }
+}
+
+impl Default for SystemTzConfig {
+    fn default() -> Self {
+        SystemTzConfig::new()
+    }
+}
+
+/// Guesses the time zone that the system is set to.
+///
+/// This is [`system_tz_with()`] for the running system, which reads the `TZ`
+/// environment variable and the files under `/etc` that Linux distributions use.
+///
+/// ```no_run
+/// let tz = chrono_tz::system_tz().unwrap_or(chrono_tz::UTC);
+/// println!("Times are shown in {tz}");
+/// ```
+pub fn system_tz() -> Result<Tz, SystemTzError> {
+    system_tz_with(&SystemTzConfig::new())
+}
+
+/// Guesses the time zone that `config` describes, trying these in order:
+///
+/// 1. The `TZ` environment variable, like the C library does: the name of a zone,
+///    optionally after a `:`, the path of a compiled zone, or the POSIX TZ string of
+///    a compiled zone. An empty `TZ` means UTC. When `TZ` is set, the files are not
+///    looked at.
+/// 2. The link at `/etc/localtime`, when it points into the zoneinfo directory, as
+///    in `/etc/localtime -> /usr/share/zoneinfo/Europe/Berlin`.
+/// 3. The name in `/etc/timezone`.
+/// 4. The compiled zone whose data `/etc/localtime` has, when it is a copy rather
+///    than a link. Its transitions since 1970 have to match, so this fails when the
+///    `filter-by-years` feature leaves some of them out.
+///
+/// Where several zones are equally good, as for a POSIX TZ string or a copy of a file
+/// that other zones have the same data as, this prefers the zone that represents a
+/// CLDR metazone, and zones to links.
+pub fn system_tz_with(config: &SystemTzConfig) -> Result<Tz, SystemTzError> {
+    if let Some(tz_var) = &config.tz_var {
+        return tz_from_var(tz_var, &config.zoneinfo)
+            .ok_or_else(|| SystemTzError::UnknownTzVar(tz_var.clone()));
+    }
+    linked_zone(&config.localtime, &config.zoneinfo)
+        .or_else(|| named_zone(&config.timezone_file))
+        .or_else(|| zone_with_data_of(&config.localtime))
+        .ok_or(SystemTzError::NotFound)
+}
+
+fn tz_from_var(tz_var: &str, zoneinfo: &Path) -> Option<Tz> {
+    if tz_var.is_empty() {
+        return Some(Tz::UTC);
+    }
+    let name = tz_var.strip_prefix(':').unwrap_or(tz_var);
+    if let Ok(tz) = name.parse() {
+        return Some(tz);
+    }
+    if Path::new(name).is_absolute() {
+        let path = Path::new(name);
+        return linked_zone(path, zoneinfo).or_else(|| zone_with_data_of(path));
+    }
+    let zones = TZ_VARIANTS.iter().copied();
+    preferred(zones.filter(|tz| tz.posix_tz_string() == Some(name))).or_else(|| {
+        // A zone that the system has, but that isn't compiled in under its name.
+        let dyn_tz = DynTz::from_zoneinfo(zoneinfo, name).ok()?;
+        matching_zone(&dyn_tz)
+    })
+}
+
+/// The zone that `path` is, or links to, in the zoneinfo directory.
+fn linked_zone(path: &Path, zoneinfo: &Path) -> Option<Tz> {
+    // Links can go through others, as with `/etc/alternatives`.
+    const MAX_LINKS: usize = 8;
+    let mut path = path.to_owned();
+    for _ in 0..MAX_LINKS {
+        if let Some(tz) = zone_in(&path, zoneinfo) {
+            return Some(tz);
+        }
+        let target = fs::read_link(&path).ok()?;
+        path = path.parent().unwrap_or(Path::new("/")).join(target);
+    }
+    None
+}
+
+/// The name of the zone at `path`, if that is in the zoneinfo directory.
+fn zone_in(path: &Path, zoneinfo: &Path) -> Option<Tz> {
+    let path = normalize(path);
+    // The zoneinfo directory can be a link itself.
+    let roots = [Some(normalize(zoneinfo)), fs::canonicalize(zoneinfo).ok()];
+    roots.into_iter().flatten().find_map(|root| {
+        let name = path.strip_prefix(root).ok()?;
+        // The `posix` and `right` directories repeat the zones without and with leap
+        // seconds.
+        let name = ["posix", "right"]
+            .iter()
+            .find_map(|dir| name.strip_prefix(dir).ok())
+            .unwrap_or(name);
+        name.to_str()?.parse().ok()
+    })
+}
+
+/// Resolves the `.` and `..` components of `path` without looking at the file system,
+/// as links inside the zoneinfo directory would otherwise lose the name of the zone.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+/// The zone named on the first line of `path`.
+fn named_zone(path: &Path) -> Option<Tz> {
+    let contents = fs::read_to_string(path).ok()?;
+    contents.lines().next()?.trim().parse().ok()
+}
+
+/// The compiled zone that has the data of the TZif file at `path`.
+fn zone_with_data_of(path: &Path) -> Option<Tz> {
+    let data = fs::read(path).ok()?;
+    matching_zone(&DynTz::from_tzif("localtime", &data).ok()?)
+}
+
+/// The compiled zone with the same offsets and abbreviations as `dyn_tz` since 1970.
+///
+/// Distributions may build their files with `backzone`, which changes the data of
+/// some zones before 1970.
+fn matching_zone(dyn_tz: &DynTz) -> Option<Tz> {
+    let start = Utc.with_ymd_and_hms(1970, 1, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2100, 1, 1, 0, 0, 0).unwrap();
+    let tz = dyn_tz.closest_tz(start..end)?;
+    let matches = dyn_tz.timespans().equivalent_between(
+        &tz.timespans(),
+        start.timestamp(),
+        end.timestamp(),
+        |a, b| Equivalence::OffsetAndAbbreviation.same(a, b),
+    );
+    matches.then_some(tz)
+}
+
+/// The zone among `zones` that represents a CLDR metazone, if any, preferring zones to
+/// links and then the first one.
+fn preferred(zones: impl Iterator<Item = Tz>) -> Option<Tz> {
+    zones
+        .enumerate()
+        .max_by_key(|&(index, tz)| (tz.is_golden_zone(), !tz.is_link(), Reverse(index)))
+        .map(|(_, tz)| tz)
+}
+
+/// An error from [`system_tz()`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SystemTzError {
+    /// `TZ` is set, but not to the name, path or POSIX TZ string of a compiled zone.
+    UnknownTzVar(String),
+    /// Neither `/etc/localtime` nor `/etc/timezone` gives a compiled zone.
+    NotFound,
+}
+
+impl Display for SystemTzError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            SystemTzError::UnknownTzVar(tz_var) => write!(f, "unknown time zone in TZ: {tz_var:?}"),
+            SystemTzError::NotFound => f.write_str("the time zone of the system was not found"),
+        }
+    }
+}
+
+impl std::error::Error for SystemTzError {}
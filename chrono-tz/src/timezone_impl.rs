@@ -1,12 +1,17 @@
 use core::cmp::Ordering;
 use core::fmt::{Debug, Display, Error, Formatter, Write};
+use core::iter::FusedIterator;
+use core::ops::Range;
 
 use chrono::{
-    DateTime, Duration, FixedOffset, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, Offset,
-    TimeZone,
+    DateTime, Datelike, Duration, FixedOffset, LocalResult, NaiveDate, NaiveDateTime, NaiveTime,
+    Offset, TimeZone, Utc,
 };
 
 use crate::binary_search::binary_search;
+use crate::compact::CompactTimespans;
+#[cfg(feature = "std")]
+use crate::owned::OwnedTimespans;
 use crate::timezones::Tz;
 
 /// Returns [`Tz::UTC`].
@@ -21,22 +26,29 @@ impl Default for Tz {
 /// For example, [`::US::Eastern`] is composed of at least two
 /// `FixedTimespan`s: `EST` and `EDT`, that are variously in effect.
 #[derive(Copy, Clone, PartialEq, Eq)]
-pub struct FixedTimespan {
+pub struct FixedTimespan<'a> {
     /// The base offset from UTC; this usually doesn't change unless the government changes something
     pub utc_offset: i32,
     /// The additional offset from UTC for this timespan; typically for daylight saving time
     pub dst_offset: i32,
     /// The name of this timezone, for example the difference between `EDT`/`EST`
-    pub name: Option<&'static str>,
+    pub name: Option<&'a str>,
 }
 
-impl Offset for FixedTimespan {
+impl FixedTimespan<'_> {
+    /// The total offset from UTC in seconds
+    pub(crate) fn total_offset(&self) -> i64 {
+        self.utc_offset as i64 + self.dst_offset as i64
+    }
+}
+
+impl Offset for FixedTimespan<'_> {
     fn fix(&self) -> FixedOffset {
         FixedOffset::east_opt(self.utc_offset + self.dst_offset).unwrap()
     }
 }
 
-impl Display for FixedTimespan {
+impl Display for FixedTimespan<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
         if let Some(name) = self.name {
             return write!(f, "{name}");
@@ -67,7 +79,7 @@ impl Display for FixedTimespan {
     }
 }
 
-impl Debug for FixedTimespan {
+impl Debug for FixedTimespan<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
         Display::fmt(self, f)
     }
@@ -76,7 +88,7 @@ impl Debug for FixedTimespan {
 #[derive(Copy, Clone, PartialEq, Eq)]
 pub struct TzOffset {
     tz: Tz,
-    offset: FixedTimespan,
+    offset: FixedTimespan<'static>,
 }
 
 /// Detailed timezone offset components that expose any special conditions currently in effect.
@@ -151,11 +163,16 @@ pub trait OffsetName {
 }
 
 impl TzOffset {
-    fn new(tz: Tz, offset: FixedTimespan) -> Self {
+    fn new(tz: Tz, offset: FixedTimespan<'static>) -> Self {
         TzOffset { tz, offset }
     }
 
-    fn map_localresult(tz: Tz, result: LocalResult<FixedTimespan>) -> LocalResult<Self> {
+    #[cfg(feature = "localized-names")]
+    pub(crate) fn tz(&self) -> Tz {
+        self.tz
+    }
+
+    fn map_localresult(tz: Tz, result: LocalResult<FixedTimespan<'static>>) -> LocalResult<Self> {
         match result {
             LocalResult::None => LocalResult::None,
             LocalResult::Single(s) => LocalResult::Single(TzOffset::new(tz, s)),
@@ -244,28 +261,88 @@ impl Span {
 }
 
 #[derive(Copy, Clone)]
-pub struct FixedTimespanSet {
-    pub first: FixedTimespan,
-    pub rest: &'static [(i64, FixedTimespan)],
+pub struct FixedTimespanSet<'a> {
+    pub first: FixedTimespan<'a>,
+    pub rest: TransitionTable<'a>,
+    /// The rule that keeps producing timespans after the last one in `rest`
+    pub extra: Option<ExtraRule<'a>>,
+}
+
+/// The transitions of a [`FixedTimespanSet`], each with the timespan that it starts
+#[derive(Copy, Clone)]
+pub enum TransitionTable<'a> {
+    /// The zones that are read at runtime, which own their abbreviations
+    #[cfg(feature = "std")]
+    Owned(&'a OwnedTimespans),
+    /// The compact encoding of the zones compiled into [`Tz`]
+    Compact(&'a CompactTimespans),
 }
 
-impl FixedTimespanSet {
+impl<'a> TransitionTable<'a> {
+    pub(crate) fn len(&self) -> usize {
+        match self {
+            #[cfg(feature = "std")]
+            TransitionTable::Owned(owned) => owned.len(),
+            TransitionTable::Compact(compact) => compact.len(),
+        }
+    }
+
+    /// The transition at `index`, which has to be less than [`TransitionTable::len`].
+    pub(crate) fn transition(&self, index: usize) -> (i64, FixedTimespan<'a>) {
+        match self {
+            #[cfg(feature = "std")]
+            TransitionTable::Owned(owned) => owned.get(index),
+            TransitionTable::Compact(compact) => compact.get(index),
+        }
+    }
+
+    /// The timespan that the transition at `index` starts.
+    fn timespan(&self, index: usize) -> FixedTimespan<'a> {
+        match self {
+            #[cfg(feature = "std")]
+            TransitionTable::Owned(owned) => owned.get(index).1,
+            TransitionTable::Compact(compact) => compact.timespan(index),
+        }
+    }
+
+    pub(crate) fn last(&self) -> Option<(i64, FixedTimespan<'a>)> {
+        let len = self.len();
+        (len > 0).then(|| self.transition(len - 1))
+    }
+
+    #[cfg(test)]
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (i64, FixedTimespan<'a>)> + '_ {
+        (0..self.len()).map(|index| self.transition(index))
+    }
+}
+
+impl<'a> FixedTimespanSet<'a> {
     fn len(&self) -> usize {
         1 + self.rest.len()
     }
 
+    // The zone's recurring rule, if it has one, takes over after the last transition
+    // in `rest`, whatever year that is in, or from the start if there are none.
+    fn extra_rule(&self, timestamp: i64) -> Option<ExtraRule<'a>> {
+        match (self.extra, self.rest.last()) {
+            (Some(extra), Some((last, _))) if timestamp >= last => Some(extra),
+            (Some(extra), None) => Some(extra),
+            _ => None,
+        }
+    }
+
     fn utc_span(&self, index: usize) -> Span {
         debug_assert!(index < self.len());
         Span {
             begin: if index == 0 {
                 None
             } else {
-                Some(self.rest[index - 1].0)
+                Some(self.rest.transition(index - 1).0)
             },
             end: if index == self.rest.len() {
                 None
             } else {
-                Some(self.rest[index].0)
+                Some(self.rest.transition(index).0)
             },
         }
     }
@@ -276,52 +353,39 @@ impl FixedTimespanSet {
             begin: if index == 0 {
                 None
             } else {
-                let span = self.rest[index - 1];
+                let span = self.rest.transition(index - 1);
                 Some(span.0 + span.1.utc_offset as i64 + span.1.dst_offset as i64)
             },
             end: if index == self.rest.len() {
                 None
-            } else if index == 0 {
-                Some(
-                    self.rest[index].0
-                        + self.first.utc_offset as i64
-                        + self.first.dst_offset as i64,
-                )
             } else {
+                let before = self.get(index);
                 Some(
-                    self.rest[index].0
-                        + self.rest[index - 1].1.utc_offset as i64
-                        + self.rest[index - 1].1.dst_offset as i64,
+                    self.rest.transition(index).0
+                        + before.utc_offset as i64
+                        + before.dst_offset as i64,
                 )
             },
         }
     }
 
-    fn get(&self, index: usize) -> FixedTimespan {
+    fn get(&self, index: usize) -> FixedTimespan<'a> {
         debug_assert!(index < self.len());
         if index == 0 {
             self.first
         } else {
-            self.rest[index - 1].1
+            self.rest.timespan(index - 1)
         }
     }
-}
-
-pub trait TimeSpans {
-    fn timespans(&self) -> FixedTimespanSet;
-}
 
-impl TimeZone for Tz {
-    type Offset = TzOffset;
-
-    fn from_offset(offset: &Self::Offset) -> Self {
-        offset.tz
-    }
-
-    #[allow(deprecated)]
-    fn offset_from_local_date(&self, local: &NaiveDate) -> LocalResult<Self::Offset> {
-        let earliest = self.offset_from_local_datetime(&local.and_time(NaiveTime::MIN));
-        let latest = self.offset_from_local_datetime(&local.and_hms_opt(23, 59, 59).unwrap());
+    pub(crate) fn timespan_at_local_date(
+        &self,
+        local: &NaiveDate,
+    ) -> LocalResult<FixedTimespan<'a>> {
+        let earliest =
+            self.timespans_at_local(local.and_time(NaiveTime::MIN).and_utc().timestamp());
+        let latest =
+            self.timespans_at_local(local.and_hms_opt(23, 59, 59).unwrap().and_utc().timestamp());
         // From the chrono docs:
         //
         // > This type should be considered ambiguous at best, due to the inherent lack of
@@ -361,47 +425,370 @@ impl TimeZone for Tz {
 
     // First search for a timespan that the local datetime falls into, then, if it exists,
     // check the two surrounding timespans (if they exist) to see if there is any ambiguity.
-    fn offset_from_local_datetime(&self, local: &NaiveDateTime) -> LocalResult<Self::Offset> {
-        let timestamp = local.and_utc().timestamp();
-        let timespans = self.timespans();
-        let index = binary_search(0, timespans.len(), |i| {
-            timespans.local_span(i).cmp(timestamp)
-        });
-        TzOffset::map_localresult(
-            *self,
-            match index {
-                Ok(0) if timespans.len() == 1 => LocalResult::Single(timespans.get(0)),
-                Ok(0) if timespans.local_span(1).contains(timestamp) => {
-                    LocalResult::Ambiguous(timespans.get(0), timespans.get(1))
+    pub(crate) fn timespans_at_local(&self, timestamp: i64) -> LocalResult<FixedTimespan<'a>> {
+        // A day later than the local time is certainly later than its UTC instant.
+        if let Some(extra) = self.extra_rule(timestamp - 24 * 60 * 60) {
+            return extra.timespans_at_local(timestamp);
+        }
+        let index = binary_search(0, self.len(), |i| self.local_span(i).cmp(timestamp));
+        match index {
+            Ok(0) if self.len() == 1 => LocalResult::Single(self.get(0)),
+            Ok(0) if self.local_span(1).contains(timestamp) => {
+                LocalResult::Ambiguous(self.get(0), self.get(1))
+            }
+            Ok(0) => LocalResult::Single(self.get(0)),
+            Ok(i) if self.local_span(i - 1).contains(timestamp) => {
+                LocalResult::Ambiguous(self.get(i - 1), self.get(i))
+            }
+            Ok(i) if i == self.len() - 1 => LocalResult::Single(self.get(i)),
+            Ok(i) if self.local_span(i + 1).contains(timestamp) => {
+                LocalResult::Ambiguous(self.get(i), self.get(i + 1))
+            }
+            Ok(i) => LocalResult::Single(self.get(i)),
+            Err(_) => LocalResult::None,
+        }
+    }
+
+    // Binary search for the required timespan. Any i64 is guaranteed to fall within
+    // exactly one timespan, no matter what (so the `unwrap` is safe).
+    pub(crate) fn timespan_at_utc(&self, timestamp: i64) -> FixedTimespan<'a> {
+        if let Some(extra) = self.extra_rule(timestamp) {
+            return extra.timespan_at(timestamp);
+        }
+        let index = binary_search(0, self.len(), |i| self.utc_span(i).cmp(timestamp)).unwrap();
+        self.get(index)
+    }
+
+    /// The first transition strictly after `timestamp`, with the timespans before
+    /// and after it.
+    pub(crate) fn next_transition(
+        &self,
+        timestamp: i64,
+    ) -> Option<(i64, FixedTimespan<'a>, FixedTimespan<'a>)> {
+        let index = binary_search(0, self.len(), |i| self.utc_span(i).cmp(timestamp)).unwrap();
+        match index < self.rest.len() {
+            true => {
+                let (at, timespan) = self.rest.transition(index);
+                Some((at, self.get(index), timespan))
+            }
+            false => self.extra?.next_transition(timestamp),
+        }
+    }
+
+    /// The last transition strictly before `timestamp`, with the timespans before
+    /// and after it.
+    pub(crate) fn previous_transition(
+        &self,
+        timestamp: i64,
+    ) -> Option<(i64, FixedTimespan<'a>, FixedTimespan<'a>)> {
+        if let Some(extra) = self.extra_rule(timestamp) {
+            let last = self.rest.last().map_or(i64::MIN, |(last, _)| last);
+            match extra.previous_transition(timestamp) {
+                Some(transition) if transition.0 > last => return Some(transition),
+                _ => {}
+            }
+        }
+
+        let timestamp = timestamp.checked_sub(1)?;
+        let index = binary_search(0, self.len(), |i| self.utc_span(i).cmp(timestamp)).unwrap();
+        match index {
+            0 => None,
+            _ => Some((
+                self.rest.transition(index - 1).0,
+                self.get(index - 1),
+                self.get(index),
+            )),
+        }
+    }
+
+    /// Whether `self` and `other` have the `same` timespans at every instant from
+    /// `start` up to `end`.
+    pub(crate) fn equivalent_between(
+        &self,
+        other: &FixedTimespanSet,
+        start: i64,
+        end: i64,
+        same: impl Fn(FixedTimespan, FixedTimespan) -> bool,
+    ) -> bool {
+        let mut at = start;
+        while at < end {
+            if !same(self.timespan_at_utc(at), other.timespan_at_utc(at)) {
+                return false;
+            }
+            at = match (self.next_transition(at), other.next_transition(at)) {
+                (Some((a, ..)), Some((b, ..))) => a.min(b),
+                (Some((a, ..)), None) => a,
+                (None, Some((b, ..))) => b,
+                (None, None) => return true,
+            };
+        }
+        true
+    }
+
+    /// The earliest timestamp from which `self` and `other` have the `same` timespans
+    /// forever, or `None` if they never do.
+    fn equivalent_since(
+        &self,
+        other: &FixedTimespanSet,
+        same: impl Fn(FixedTimespan, FixedTimespan) -> bool,
+    ) -> Option<i64> {
+        // After their last transitions in `rest`, both sets either keep their last
+        // timespan or follow a yearly rule. The calendar repeats every 400 years, so if
+        // they agree for that long after both got there, they agree forever.
+        const DAYS_PER_400_YEARS: i64 = 146_097;
+        let min = DateTime::<Utc>::MIN_UTC.timestamp();
+        let last = |set: &FixedTimespanSet| set.rest.last().map_or(min, |(at, _)| at);
+        let mut since = last(self).max(last(other));
+        let end = since + DAYS_PER_400_YEARS * 24 * 60 * 60;
+        if !self.equivalent_between(other, since, end, &same) {
+            return None;
+        }
+
+        loop {
+            let previous = match (
+                self.previous_transition(since),
+                other.previous_transition(since),
+            ) {
+                (Some((a, ..)), Some((b, ..))) => a.max(b),
+                (Some((a, ..)), None) => a,
+                (None, Some((b, ..))) => b,
+                (None, None) => return Some(min),
+            };
+            if !same(
+                self.timespan_at_utc(previous),
+                other.timespan_at_utc(previous),
+            ) {
+                return Some(since);
+            }
+            since = previous;
+        }
+    }
+
+    /// The transition that skips or repeats the local time `local`, if any.
+    fn transition_at_local(
+        &self,
+        local: i64,
+    ) -> Option<(i64, FixedTimespan<'a>, FixedTimespan<'a>)> {
+        // Offsets are less than a day, so such a transition is less than a day away.
+        let mut timestamp = local - 24 * 60 * 60;
+        loop {
+            let (at, before, after) = self.next_transition(timestamp)?;
+            if at >= local + 24 * 60 * 60 {
+                return None;
+            }
+            let (before_local, after_local) =
+                (at + before.total_offset(), at + after.total_offset());
+            if before_local.min(after_local) <= local && local < before_local.max(after_local) {
+                return Some((at, before, after));
+            }
+            timestamp = at;
+        }
+    }
+}
+
+/// The rule that applies after the last transition of a [`FixedTimespanSet`]
+///
+/// This carries the same information as the POSIX TZ string footer of a compiled
+/// TZif file: a standard timespan, and optionally a daylight saving timespan that
+/// recurs every year.
+#[derive(Copy, Clone)]
+pub struct ExtraRule<'a> {
+    /// The timespan in effect outside of daylight saving time
+    pub standard: FixedTimespan<'a>,
+    /// The daylight saving timespan and when it starts and ends each year
+    pub daylight: Option<DaylightRule<'a>>,
+}
+
+#[derive(Copy, Clone)]
+pub struct DaylightRule<'a> {
+    /// The timespan in effect between `start` and `end`
+    pub timespan: FixedTimespan<'a>,
+    /// When `timespan` starts, in the local time of the standard timespan
+    pub start: RuleTransition,
+    /// When `timespan` ends, in the local time of `timespan` itself
+    pub end: RuleTransition,
+}
+
+/// A yearly transition, given as a day of the year and a local time on that day
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct RuleTransition {
+    pub date: RuleDate,
+    /// Seconds after local midnight; may be negative or exceed 24 hours
+    pub time: i32,
+}
+
+/// A day of the year in one of the forms of a POSIX TZ string
+#[derive(Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(not(feature = "std"), allow(dead_code))] // only TZ strings use the other forms
+pub enum RuleDate {
+    /// `Jn`: the one-based day of the year, never counting February 29th
+    JulianDay(u16),
+    /// `n`: the zero-based day of the year, counting February 29th in leap years
+    DayOfYear(u16),
+    /// `Mm.w.d`: weekday `d` (0 is Sunday) of week `w` of month `m`, where week 5
+    /// is the last such weekday of the month
+    MonthWeekday { month: u8, week: u8, weekday: u8 },
+}
+
+impl RuleDate {
+    /// The timestamp of local midnight on this date, as if the local time was UTC.
+    fn midnight(self, year: i32) -> Option<i64> {
+        let date = match self {
+            RuleDate::JulianDay(day) => {
+                let is_leap = NaiveDate::from_ymd_opt(year, 2, 29).is_some();
+                let day = if is_leap && day >= 60 { day + 1 } else { day };
+                NaiveDate::from_yo_opt(year, day as u32)?
+            }
+            RuleDate::DayOfYear(day) => NaiveDate::from_yo_opt(year, day as u32 + 1)?,
+            RuleDate::MonthWeekday {
+                month,
+                week,
+                weekday,
+            } => {
+                let first = NaiveDate::from_ymd_opt(year, month as u32, 1)?;
+                let first_weekday = first.weekday().num_days_from_sunday();
+                let day = 1 + (weekday as u32 + 7 - first_weekday) % 7 + (week as u32 - 1) * 7;
+                match NaiveDate::from_ymd_opt(year, month as u32, day) {
+                    Some(date) => date,
+                    None => NaiveDate::from_ymd_opt(year, month as u32, day - 7)?,
+                }
+            }
+        };
+        Some(date.and_time(NaiveTime::MIN).and_utc().timestamp())
+    }
+}
+
+impl<'a> ExtraRule<'a> {
+    /// The two transitions of `year` as UTC timestamps, in chronological order,
+    /// each with the timespan that starts at that instant.
+    fn transitions_in_year(&self, year: i32) -> Option<[(i64, FixedTimespan<'a>); 2]> {
+        let daylight = self.daylight?;
+        let start = daylight.start.date.midnight(year)? + daylight.start.time as i64
+            - self.standard.total_offset();
+        let end = daylight.end.date.midnight(year)? + daylight.end.time as i64
+            - daylight.timespan.total_offset();
+        Some(match start < end {
+            true => [(start, daylight.timespan), (end, self.standard)],
+            false => [(end, self.standard), (start, daylight.timespan)],
+        })
+    }
+
+    pub(crate) fn timespan_at(&self, timestamp: i64) -> FixedTimespan<'a> {
+        let year = match DateTime::from_timestamp(timestamp, 0) {
+            Some(dt) if self.daylight.is_some() => dt.year(),
+            _ => return self.standard,
+        };
+
+        // Transitions close to new year can fall in the neighbouring year in UTC.
+        let mut current = self.standard;
+        for year in year - 1..=year + 1 {
+            for (start, timespan) in self.transitions_in_year(year).into_iter().flatten() {
+                if start <= timestamp {
+                    current = timespan;
                 }
-                Ok(0) => LocalResult::Single(timespans.get(0)),
-                Ok(i) if timespans.local_span(i - 1).contains(timestamp) => {
-                    LocalResult::Ambiguous(timespans.get(i - 1), timespans.get(i))
+            }
+        }
+        current
+    }
+
+    /// The first transition strictly after `timestamp`.
+    pub(crate) fn next_transition(
+        &self,
+        timestamp: i64,
+    ) -> Option<(i64, FixedTimespan<'a>, FixedTimespan<'a>)> {
+        let daylight = self.daylight?;
+        let year = DateTime::from_timestamp(timestamp, 0)?.year();
+        for year in year - 1..=year + 1 {
+            for (at, timespan) in self.transitions_in_year(year).into_iter().flatten() {
+                if at > timestamp {
+                    let before = match timespan == self.standard {
+                        true => daylight.timespan,
+                        false => self.standard,
+                    };
+                    return Some((at, before, timespan));
                 }
-                Ok(i) if i == timespans.len() - 1 => LocalResult::Single(timespans.get(i)),
-                Ok(i) if timespans.local_span(i + 1).contains(timestamp) => {
-                    LocalResult::Ambiguous(timespans.get(i), timespans.get(i + 1))
+            }
+        }
+        None
+    }
+
+    /// The last transition strictly before `timestamp`.
+    fn previous_transition(
+        &self,
+        timestamp: i64,
+    ) -> Option<(i64, FixedTimespan<'a>, FixedTimespan<'a>)> {
+        let daylight = self.daylight?;
+        let year = DateTime::from_timestamp(timestamp, 0)?.year();
+        for year in (year - 1..=year + 1).rev() {
+            for (at, timespan) in self.transitions_in_year(year).into_iter().flatten().rev() {
+                if at < timestamp {
+                    let before = match timespan == self.standard {
+                        true => daylight.timespan,
+                        false => self.standard,
+                    };
+                    return Some((at, before, timespan));
                 }
-                Ok(i) => LocalResult::Single(timespans.get(i)),
-                Err(_) => LocalResult::None,
-            },
-        )
+            }
+        }
+        None
+    }
+
+    // A local time can only have one of the two offsets, so try both of them and
+    // keep those that map back onto themselves.
+    fn timespans_at_local(&self, timestamp: i64) -> LocalResult<FixedTimespan<'a>> {
+        let daylight = match self.daylight {
+            Some(daylight) => daylight.timespan,
+            None => return LocalResult::Single(self.standard),
+        };
+
+        let mut found = [daylight, self.standard]
+            .into_iter()
+            .filter_map(|timespan| {
+                let utc = timestamp - timespan.total_offset();
+                (self.timespan_at(utc) == timespan).then_some((utc, timespan))
+            });
+        match (found.next(), found.next()) {
+            (Some((a_utc, a)), Some((b_utc, b))) if b_utc < a_utc => LocalResult::Ambiguous(b, a),
+            (Some((_, a)), Some((_, b))) => LocalResult::Ambiguous(a, b),
+            (Some((_, a)), None) => LocalResult::Single(a),
+            _ => LocalResult::None,
+        }
+    }
+}
+
+/// The timespans of a zone that is read at runtime; [`Tz`] has an inherent `timespans`.
+#[cfg(feature = "std")]
+pub trait TimeSpans {
+    fn timespans(&self) -> FixedTimespanSet<'_>;
+}
+
+impl TimeZone for Tz {
+    type Offset = TzOffset;
+
+    fn from_offset(offset: &Self::Offset) -> Self {
+        offset.tz
+    }
+
+    #[allow(deprecated)]
+    fn offset_from_local_date(&self, local: &NaiveDate) -> LocalResult<Self::Offset> {
+        TzOffset::map_localresult(*self, self.timespans().timespan_at_local_date(local))
+    }
+
+    fn offset_from_local_datetime(&self, local: &NaiveDateTime) -> LocalResult<Self::Offset> {
+        let timestamp = local.and_utc().timestamp();
+        TzOffset::map_localresult(*self, self.timespans().timespans_at_local(timestamp))
     }
 
     #[allow(deprecated)]
     fn offset_from_utc_date(&self, utc: &NaiveDate) -> Self::Offset {
-        // See comment above for why it is OK to just take any arbitrary time in the day
+        // See comment on `FixedTimespanSet::timespan_at_local_date` for why it is OK
+        // to just take any arbitrary time in the day
         self.offset_from_utc_datetime(&utc.and_time(NaiveTime::MIN))
     }
 
-    // Binary search for the required timespan. Any i64 is guaranteed to fall within
-    // exactly one timespan, no matter what (so the `unwrap` is safe).
     fn offset_from_utc_datetime(&self, dt: &NaiveDateTime) -> Self::Offset {
         let timestamp = dt.and_utc().timestamp();
-        let timespans = self.timespans();
-        let index =
-            binary_search(0, timespans.len(), |i| timespans.utc_span(i).cmp(timestamp)).unwrap();
-        TzOffset::new(*self, timespans.get(index))
+        TzOffset::new(*self, self.timespans().timespan_at_utc(timestamp))
     }
 }
 
@@ -433,6 +820,16 @@ impl GapInfo {
     pub fn new(local: &NaiveDateTime, tz: &Tz) -> Option<Self> {
         let timestamp = local.and_utc().timestamp();
         let timespans = tz.timespans();
+        if timespans.extra_rule(timestamp - 24 * 60 * 60).is_some() {
+            let (at, before, after) = timespans.transition_at_local(timestamp)?;
+            if after.total_offset() < before.total_offset() {
+                return None;
+            }
+            let begin = DateTime::from_timestamp(at + before.total_offset(), 0)
+                .map(|begin| (begin.naive_utc(), TzOffset::new(*tz, before)));
+            let end = DateTime::from_timestamp(at, 0).map(|end| end.with_timezone(tz));
+            return Some(Self { begin, end });
+        }
         let index = binary_search(0, timespans.len(), |i| {
             timespans.local_span(i).cmp(timestamp)
         });
@@ -476,3 +873,391 @@ impl GapInfo {
         Some(Self { begin, end })
     }
 }
+
+/// Represents the information of a fold.
+///
+/// This is the counterpart of [`GapInfo`] for a local [`NaiveDateTime`] that occurs twice,
+/// for which [`TimeZone::from_local_datetime`] returns [`LocalResult::Ambiguous`].
+pub struct FoldInfo {
+    /// The first local time that occurs twice.
+    pub begin: NaiveDateTime,
+    /// The first local time after `begin` that only occurs once again.
+    pub end: NaiveDateTime,
+    /// The first occurrence of the local time, with the offset before the transition.
+    pub earlier: DateTime<Tz>,
+    /// The second occurrence of the local time, with the offset after the transition.
+    pub later: DateTime<Tz>,
+}
+
+impl FoldInfo {
+    /// Return information about a fold.
+    ///
+    /// It returns `None` if `local` does not occur twice in the current timezone.
+    pub fn new(local: &NaiveDateTime, tz: &Tz) -> Option<Self> {
+        let timestamp = local.and_utc().timestamp();
+        let timespans = tz.timespans();
+        let (at, before, after) = if timespans.extra_rule(timestamp - 24 * 60 * 60).is_some() {
+            timespans.transition_at_local(timestamp)?
+        } else {
+            let index = binary_search(0, timespans.len(), |i| {
+                timespans.local_span(i).cmp(timestamp)
+            })
+            .ok()?;
+
+            let index = match index {
+                _ if index > 0 && timespans.local_span(index - 1).contains(timestamp) => index,
+                _ if index + 1 < timespans.len()
+                    && timespans.local_span(index + 1).contains(timestamp) =>
+                {
+                    index + 1
+                }
+                _ => return None,
+            };
+            let (at, after) = timespans.rest.transition(index - 1);
+            (at, timespans.get(index - 1), after)
+        };
+
+        if after.total_offset() >= before.total_offset() {
+            return None;
+        }
+        let local_time =
+            |timestamp| DateTime::from_timestamp(timestamp, 0).map(|dt| dt.naive_utc());
+        let instant = |timespan: FixedTimespan| {
+            local
+                .checked_sub_signed(Duration::seconds(timespan.total_offset()))
+                .map(|utc| tz.from_utc_datetime(&utc))
+        };
+        Some(Self {
+            begin: local_time(at + after.total_offset())?,
+            end: local_time(at + before.total_offset())?,
+            earlier: instant(before)?,
+            later: instant(after)?,
+        })
+    }
+}
+
+/// How to pick an instant for a local time that is ambiguous or doesn't exist.
+///
+/// These are the options of the Temporal proposal for JavaScript. The default,
+/// [`Disambiguation::Compatible`], also matches the rules of RFC 5545 for iCalendar.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Disambiguation {
+    /// Use the earlier instant for a repeated local time, and move a skipped local
+    /// time forward by the length of the gap.
+    #[default]
+    Compatible,
+    /// Use the earlier instant for a repeated local time, and move a skipped local
+    /// time backward by the length of the gap.
+    Earlier,
+    /// Use the later instant for a repeated local time, and move a skipped local
+    /// time forward by the length of the gap.
+    Later,
+    /// Return an error for a local time that is repeated or skipped.
+    Reject,
+}
+
+/// The error returned by [`Tz::resolve_local`] with [`Disambiguation::Reject`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ResolveError {
+    /// The local time falls in a gap, for example when clocks go forward.
+    Skipped,
+    /// The local time occurs twice, for example when clocks go back.
+    Ambiguous,
+}
+
+impl Display for ResolveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        f.write_str(match self {
+            ResolveError::Skipped => "local time does not exist in this timezone",
+            ResolveError::Ambiguous => "local time is ambiguous in this timezone",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ResolveError {}
+
+impl Tz {
+    /// Converts a local time to a timezone-aware [`DateTime`], using `disambiguation`
+    /// to choose an instant when the local time is repeated or skipped.
+    ///
+    /// ```
+    /// # extern crate chrono;
+    /// # extern crate chrono_tz;
+    /// use chrono::NaiveDate;
+    /// use chrono_tz::Disambiguation;
+    /// use chrono_tz::Europe::London;
+    ///
+    /// # fn main() {
+    /// // Clocks in London went forward from 01:00 to 02:00 on this day
+    /// let skipped = NaiveDate::from_ymd_opt(2016, 3, 27)
+    ///     .unwrap()
+    ///     .and_hms_opt(1, 30, 0)
+    ///     .unwrap();
+    /// let later = London.resolve_local(&skipped, Disambiguation::Compatible);
+    /// assert_eq!(later.unwrap().to_string(), "2016-03-27 02:30:00 BST");
+    /// let earlier = London.resolve_local(&skipped, Disambiguation::Earlier);
+    /// assert_eq!(earlier.unwrap().to_string(), "2016-03-27 00:30:00 GMT");
+    /// assert!(London.resolve_local(&skipped, Disambiguation::Reject).is_err());
+    /// # }
+    /// ```
+    pub fn resolve_local(
+        &self,
+        local: &NaiveDateTime,
+        disambiguation: Disambiguation,
+    ) -> Result<DateTime<Tz>, ResolveError> {
+        use Disambiguation::*;
+        match (self.from_local_datetime(local), disambiguation) {
+            (LocalResult::Single(dt), _) => Ok(dt),
+            (LocalResult::Ambiguous(earlier, _), Compatible | Earlier) => Ok(earlier),
+            (LocalResult::Ambiguous(_, later), Later) => Ok(later),
+            (LocalResult::Ambiguous(..), Reject) => Err(ResolveError::Ambiguous),
+            (LocalResult::None, Reject) => Err(ResolveError::Skipped),
+            (LocalResult::None, _) => {
+                let gap = GapInfo::new(local, self).ok_or(ResolveError::Skipped)?;
+                let (Some((_, before)), Some(end)) = (gap.begin, gap.end) else {
+                    return Err(ResolveError::Skipped);
+                };
+                // Reading the local time with the offset from before the gap moves it
+                // forward by the length of the gap, and vice versa.
+                let offset = match disambiguation {
+                    Earlier => end.offset().fix(),
+                    _ => before.fix(),
+                };
+                let utc = *local - Duration::seconds(offset.local_minus_utc() as i64);
+                Ok(self.from_utc_datetime(&utc))
+            }
+        }
+    }
+}
+
+/// A change of offset in a time zone.
+///
+/// Returned by the iterators from [`Tz::transitions`] and [`Tz::transitions_between`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Transition {
+    /// The instant at which the new offset takes effect.
+    pub at: DateTime<Utc>,
+    /// The offset in effect until just before `at`.
+    pub before: TzOffset,
+    /// The offset in effect from `at` onwards.
+    pub after: TzOffset,
+}
+
+impl Transition {
+    fn new(
+        tz: Tz,
+        (at, before, after): (i64, FixedTimespan<'static>, FixedTimespan<'static>),
+    ) -> Option<Self> {
+        Some(Transition {
+            at: DateTime::from_timestamp(at, 0)?,
+            before: TzOffset::new(tz, before),
+            after: TzOffset::new(tz, after),
+        })
+    }
+}
+
+/// An iterator over the [`Transition`]s of a time zone, in chronological order.
+///
+/// This iterator can be consumed from both ends. Transitions after 2099 are computed
+/// from the zone's recurring rules, so going backwards from the end of time may take
+/// a while; use [`Tz::transitions_between`] to limit the range instead.
+#[derive(Clone)]
+pub struct Transitions {
+    tz: Tz,
+    /// Only transitions strictly after this timestamp are left.
+    after: i64,
+    /// Only transitions strictly before this timestamp are left.
+    before: i64,
+}
+
+impl Iterator for Transitions {
+    type Item = Transition;
+
+    fn next(&mut self) -> Option<Transition> {
+        let transition = self.tz.timespans().next_transition(self.after)?;
+        if transition.0 >= self.before {
+            self.after = self.before;
+            return None;
+        }
+        self.after = transition.0;
+        Transition::new(self.tz, transition)
+    }
+}
+
+impl DoubleEndedIterator for Transitions {
+    fn next_back(&mut self) -> Option<Transition> {
+        let transition = self.tz.timespans().previous_transition(self.before)?;
+        if transition.0 <= self.after {
+            self.before = self.after;
+            return None;
+        }
+        self.before = transition.0;
+        Transition::new(self.tz, transition)
+    }
+}
+
+impl FusedIterator for Transitions {}
+
+impl Tz {
+    /// Returns an iterator over every transition of this time zone.
+    ///
+    /// For a zone that still changes its clocks, the rules keep producing transitions
+    /// until the last year that [`DateTime<Utc>`] can represent, so the iterator is
+    /// effectively endless: `Europe/Berlin` has more than half a million transitions.
+    /// Stop it with [`Iterator::take_while`], or use [`Tz::transitions_between`].
+    ///
+    /// ```
+    /// # extern crate chrono;
+    /// # extern crate chrono_tz;
+    /// use chrono::{TimeZone, Utc};
+    /// use chrono_tz::Europe::London;
+    /// use chrono_tz::OffsetName;
+    ///
+    /// # fn main() {
+    /// let first = London.transitions().next().unwrap();
+    /// assert_eq!(first.at, Utc.with_ymd_and_hms(1847, 12, 1, 0, 1, 15).unwrap());
+    /// assert_eq!(first.before.abbreviation(), Some("LMT"));
+    /// assert_eq!(first.after.abbreviation(), Some("GMT"));
+    /// # }
+    /// ```
+    pub fn transitions(self) -> Transitions {
+        Transitions {
+            tz: self,
+            after: DateTime::<Utc>::MIN_UTC.timestamp(),
+            before: DateTime::<Utc>::MAX_UTC.timestamp(),
+        }
+    }
+
+    /// Returns an iterator over the transitions of this time zone that happen at or
+    /// after `start` and before `end`.
+    ///
+    /// ```
+    /// # extern crate chrono;
+    /// # extern crate chrono_tz;
+    /// use chrono::{TimeZone, Utc};
+    /// use chrono_tz::Europe::London;
+    ///
+    /// # fn main() {
+    /// let start = Utc.with_ymd_and_hms(2016, 1, 1, 0, 0, 0).unwrap();
+    /// let end = Utc.with_ymd_and_hms(2017, 1, 1, 0, 0, 0).unwrap();
+    /// let changes: Vec<_> = London.transitions_between(start, end).map(|t| t.at).collect();
+    /// assert_eq!(
+    ///     changes,
+    ///     [
+    ///         Utc.with_ymd_and_hms(2016, 3, 27, 1, 0, 0).unwrap(),
+    ///         Utc.with_ymd_and_hms(2016, 10, 30, 1, 0, 0).unwrap(),
+    ///     ]
+    /// );
+    /// # }
+    /// ```
+    pub fn transitions_between(self, start: DateTime<Utc>, end: DateTime<Utc>) -> Transitions {
+        Transitions {
+            tz: self,
+            after: ceil_timestamp(start) - 1,
+            before: ceil_timestamp(end),
+        }
+    }
+
+    /// Returns the first transition of this time zone after `after`.
+    ///
+    /// This returns `None` if the offset never changes again.
+    ///
+    /// ```
+    /// # extern crate chrono;
+    /// # extern crate chrono_tz;
+    /// use chrono::{TimeZone, Utc};
+    /// use chrono_tz::America::New_York;
+    /// use chrono_tz::OffsetName;
+    ///
+    /// # fn main() {
+    /// let now = Utc.with_ymd_and_hms(2024, 7, 1, 0, 0, 0).unwrap();
+    /// let next = New_York.next_transition(now).unwrap();
+    /// assert_eq!(next.at, Utc.with_ymd_and_hms(2024, 11, 3, 6, 0, 0).unwrap());
+    /// assert_eq!(next.before.abbreviation(), Some("EDT"));
+    /// assert_eq!(next.after.abbreviation(), Some("EST"));
+    /// # }
+    /// ```
+    pub fn next_transition(&self, after: DateTime<Utc>) -> Option<Transition> {
+        let transition = self.timespans().next_transition(after.timestamp())?;
+        Transition::new(*self, transition)
+    }
+
+    /// Returns the last transition of this time zone before `before`.
+    ///
+    /// This returns `None` if the offset has never changed before.
+    pub fn previous_transition(&self, before: DateTime<Utc>) -> Option<Transition> {
+        let transition = self
+            .timespans()
+            .previous_transition(ceil_timestamp(before))?;
+        Transition::new(*self, transition)
+    }
+}
+
+/// What [`Tz::equivalent_since`] and [`Tz::equivalent_between`] compare to decide
+/// whether two time zones agree.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Equivalence {
+    /// Only the total offset from UTC, so that the zones always show the same local
+    /// time.
+    #[default]
+    Offset,
+    /// The total offset from UTC and the abbreviation, such as `CET`.
+    OffsetAndAbbreviation,
+}
+
+impl Equivalence {
+    pub(crate) fn same(self, a: FixedTimespan, b: FixedTimespan) -> bool {
+        a.total_offset() == b.total_offset() && (self == Equivalence::Offset || a.name == b.name)
+    }
+}
+
+impl Tz {
+    /// Returns the instant since which this time zone and `other` have agreed, and
+    /// will keep agreeing, or `None` if they disagree at some point in the future.
+    ///
+    /// Time zones that have always agreed return [`DateTime::<Utc>::MIN_UTC`].
+    /// Unlike [`Tz::canonical`], this finds zones that are distinct in the tz
+    /// database but currently keep the same clocks.
+    ///
+    /// ```
+    /// # extern crate chrono;
+    /// # extern crate chrono_tz;
+    /// use chrono::{TimeZone, Utc};
+    /// use chrono_tz::Equivalence;
+    /// use chrono_tz::Europe::{Berlin, Paris};
+    ///
+    /// # fn main() {
+    /// // France had daylight saving time in 1979, but West Germany only from 1980.
+    /// let since = Paris.equivalent_since(Berlin, Equivalence::Offset).unwrap();
+    /// assert_eq!(since, Utc.with_ymd_and_hms(1979, 9, 30, 1, 0, 0).unwrap());
+    /// # }
+    /// ```
+    pub fn equivalent_since(self, other: Tz, equivalence: Equivalence) -> Option<DateTime<Utc>> {
+        let since = self
+            .timespans()
+            .equivalent_since(&other.timespans(), |a, b| equivalence.same(a, b))?;
+        DateTime::from_timestamp(since, 0)
+    }
+
+    /// Returns whether this time zone and `other` agree at every instant in `range`.
+    pub fn equivalent_between(
+        self,
+        other: Tz,
+        range: Range<DateTime<Utc>>,
+        equivalence: Equivalence,
+    ) -> bool {
+        self.timespans().equivalent_between(
+            &other.timespans(),
+            range.start.timestamp(),
+            ceil_timestamp(range.end),
+            |a, b| equivalence.same(a, b),
+        )
+    }
+}
+
+// Transitions happen on whole seconds, so an instant with a fraction of a second is
+// after the transition at its whole second.
+pub(crate) fn ceil_timestamp(dt: DateTime<Utc>) -> i64 {
+    dt.timestamp() + (dt.timestamp_subsec_nanos() > 0) as i64
+}
@@ -0,0 +1,456 @@
+//! Time zones loaded at runtime from TZif files, as described in RFC 8536
+
+use std::fmt::{self, Debug, Display, Formatter};
+use std::path::{Component, Path};
+use std::sync::Arc;
+use std::{fs, io};
+
+use chrono::{
+    Duration, FixedOffset, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, Offset, TimeZone,
+};
+
+use crate::owned::{LocalType, OwnedTimespans};
+use crate::posix;
+use crate::timezone_impl::{ExtraRule, FixedTimespan, FixedTimespanSet, TimeSpans};
+use crate::{OffsetComponents, OffsetName};
+
+/// A time zone loaded at runtime from a TZif file.
+///
+/// [`Tz`](crate::Tz) only knows the rules of the time zone database that chrono-tz was
+/// built with. A `DynTz` is read from the compiled TZif files that most systems install
+/// in `/usr/share/zoneinfo` instead, so loading a zone again picks up rule changes from
+/// an update of the system's tzdata package.
+///
+/// ```no_run
+/// # extern crate chrono;
+/// # extern crate chrono_tz;
+/// use chrono::TimeZone;
+/// use chrono_tz::DynTz;
+///
+/// # fn main() {
+/// let london = DynTz::from_zoneinfo("/usr/share/zoneinfo", "Europe/London").unwrap();
+/// let dt = london.with_ymd_and_hms(2016, 5, 10, 12, 0, 0).unwrap();
+/// assert_eq!(dt.to_string(), "2016-05-10 12:00:00 BST");
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct DynTz {
+    name: Arc<str>,
+    /// Shared with the links of the zone, which only differ by their name.
+    timespans: Arc<OwnedTimespans>,
+}
+
+impl DynTz {
+    /// Parses the contents of a TZif file of version 1 to 4.
+    ///
+    /// `name` is the identifier of the zone, as returned by [`DynTz::name`].
+    pub fn from_tzif(name: &str, data: &[u8]) -> Result<Self, TzifError> {
+        let mut reader = Reader(data);
+        let header = reader.header()?;
+        let mut block = reader.block(&header, 4)?;
+        let mut extra = None;
+        // Version 2 and later repeat the data with 64-bit times, followed by a footer.
+        if header.version >= 2 {
+            let header = reader.header()?;
+            block = reader.block(&header, 8)?;
+            extra = reader.footer()?;
+        }
+
+        let (first, rest) = block.timespans()?;
+        Ok(DynTz::from_timespans(name, first, &rest, extra))
+    }
+
+    /// Loads the zone `name`, such as `Europe/London`, from the directory `root`.
+    ///
+    /// `root` is usually `/usr/share/zoneinfo`. Names that would point outside of it
+    /// are rejected.
+    pub fn from_zoneinfo(root: impl AsRef<Path>, name: &str) -> Result<Self, TzifError> {
+        let path = Path::new(name);
+        if name.is_empty()
+            || !path
+                .components()
+                .all(|component| matches!(component, Component::Normal(_)))
+        {
+            return Err(TzifError::InvalidName);
+        }
+        let data = fs::read(root.as_ref().join(path))?;
+        DynTz::from_tzif(name, &data)
+    }
+
+    /// A zone that starts with `first` and switches to the timespans of `rest` at their
+    /// timestamps, for sources other than TZif files.
+    pub(crate) fn from_timespans(
+        name: &str,
+        first: FixedTimespan,
+        rest: &[(i64, FixedTimespan)],
+        extra: Option<ExtraRule>,
+    ) -> Self {
+        DynTz {
+            name: name.into(),
+            timespans: Arc::new(OwnedTimespans::new(first, rest, extra)),
+        }
+    }
+
+    /// This zone under the name `name`, as for a link to it.
+    pub(crate) fn with_name(&self, name: &str) -> Self {
+        DynTz {
+            name: name.into(),
+            timespans: self.timespans.clone(),
+        }
+    }
+
+    /// The identifier of this zone, for example `Europe/London`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl TimeSpans for DynTz {
+    fn timespans(&self) -> FixedTimespanSet<'_> {
+        self.timespans.timespans()
+    }
+}
+
+impl Debug for DynTz {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl Display for DynTz {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl TimeZone for DynTz {
+    type Offset = DynTzOffset;
+
+    fn from_offset(offset: &Self::Offset) -> Self {
+        offset.tz.clone()
+    }
+
+    #[allow(deprecated)]
+    fn offset_from_local_date(&self, local: &NaiveDate) -> LocalResult<Self::Offset> {
+        self.timespans()
+            .timespan_at_local_date(local)
+            .map(|offset| DynTzOffset::new(self.clone(), offset))
+    }
+
+    fn offset_from_local_datetime(&self, local: &NaiveDateTime) -> LocalResult<Self::Offset> {
+        self.timespans()
+            .timespans_at_local(local.and_utc().timestamp())
+            .map(|offset| DynTzOffset::new(self.clone(), offset))
+    }
+
+    #[allow(deprecated)]
+    fn offset_from_utc_date(&self, utc: &NaiveDate) -> Self::Offset {
+        self.offset_from_utc_datetime(&utc.and_time(NaiveTime::MIN))
+    }
+
+    fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> Self::Offset {
+        let timestamp = utc.and_utc().timestamp();
+        DynTzOffset::new(self.clone(), self.timespans().timespan_at_utc(timestamp))
+    }
+}
+
+/// The offset of a [`DynTz`] at some instant.
+#[derive(Clone)]
+pub struct DynTzOffset {
+    tz: DynTz,
+    offset: LocalType,
+}
+
+impl DynTzOffset {
+    fn new(tz: DynTz, offset: FixedTimespan) -> Self {
+        let offset = tz.timespans.local_type(offset);
+        DynTzOffset { tz, offset }
+    }
+
+    fn timespan(&self) -> FixedTimespan<'_> {
+        self.tz.timespans.timespan(self.offset)
+    }
+}
+
+impl OffsetComponents for DynTzOffset {
+    fn base_utc_offset(&self) -> Duration {
+        Duration::seconds(self.timespan().utc_offset as i64)
+    }
+
+    fn dst_offset(&self) -> Duration {
+        Duration::seconds(self.timespan().dst_offset as i64)
+    }
+}
+
+impl OffsetName for DynTzOffset {
+    fn tz_id(&self) -> &str {
+        self.tz.name()
+    }
+
+    fn abbreviation(&self) -> Option<&str> {
+        self.timespan().name
+    }
+}
+
+impl Offset for DynTzOffset {
+    fn fix(&self) -> FixedOffset {
+        self.timespan().fix()
+    }
+}
+
+impl Display for DynTzOffset {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.timespan(), f)
+    }
+}
+
+impl Debug for DynTzOffset {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.timespan(), f)
+    }
+}
+
+/// An error while loading a [`DynTz`].
+#[derive(Debug)]
+pub enum TzifError {
+    /// The zone name is empty or does not stay inside the zoneinfo directory.
+    InvalidName,
+    /// The TZif file could not be read.
+    Io(io::Error),
+    /// The data is not a valid TZif file, or uses a feature that is not supported.
+    InvalidData(&'static str),
+}
+
+impl Display for TzifError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            TzifError::InvalidName => f.write_str("invalid time zone name"),
+            TzifError::Io(error) => write!(f, "failed to read TZif file: {error}"),
+            TzifError::InvalidData(reason) => write!(f, "invalid TZif data: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for TzifError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TzifError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for TzifError {
+    fn from(error: io::Error) -> Self {
+        TzifError::Io(error)
+    }
+}
+
+struct Header {
+    version: u8,
+    isutcnt: usize,
+    isstdcnt: usize,
+    leapcnt: usize,
+    timecnt: usize,
+    typecnt: usize,
+    charcnt: usize,
+}
+
+/// The data block of a TZif file, which follows each header.
+struct Block<'a> {
+    /// The transition times, each with the index of the local time type it switches to.
+    transitions: Vec<(i64, usize)>,
+    /// The local time types: the offset from UTC, whether it is daylight saving time,
+    /// and its abbreviation.
+    types: Vec<(i32, bool, &'a str)>,
+}
+
+impl<'a> Block<'a> {
+    /// Converts the local time types into the timespan before the first transition and the timespan after each of them.
+    ///
+    /// TZif files only say whether a type is daylight saving time, not how much of its
+    /// offset comes from it. Like Python's `zoneinfo`, take that from the difference with
+    /// the standard time right before or after it. Zones can change their standard offset
+    /// at the same time, so use the smallest difference, and otherwise what was found for
+    /// other uses of the same type, or one hour.
+    fn timespans(&self) -> Result<(FixedTimespan<'a>, Vec<(i64, FixedTimespan<'a>)>), TzifError> {
+        let dst_offsets: Vec<_> = (0..self.transitions.len())
+            .map(|i| self.dst_offset(i))
+            .collect();
+        let mut type_dst_offsets = vec![None; self.types.len()];
+        for (&(_, index), &dst_offset) in self.transitions.iter().zip(&dst_offsets) {
+            type_dst_offsets[index] = smallest(type_dst_offsets[index], dst_offset);
+        }
+
+        let timespan = |index: usize, dst_offset: Option<i32>| {
+            let (offset, is_dst, name) = self.types[index];
+            // chrono's `FixedOffset` needs to be less than a day.
+            if offset.abs() >= 24 * 60 * 60 {
+                return Err(TzifError::InvalidData("offset is a day or more"));
+            }
+            let dst_offset = match is_dst {
+                true => dst_offset.or(type_dst_offsets[index]).unwrap_or(60 * 60),
+                false => 0,
+            };
+            Ok(FixedTimespan {
+                utc_offset: offset - dst_offset,
+                dst_offset,
+                name: Some(name),
+            })
+        };
+
+        let first = timespan(0, None)?;
+        let rest = self
+            .transitions
+            .iter()
+            .zip(dst_offsets)
+            .map(|(&(at, index), dst_offset)| {
+                timespan(index, dst_offset).map(|timespan| (at, timespan))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok((first, rest))
+    }
+
+    /// The daylight saving part of the offset after transition `i`, from the standard
+    /// time types around it.
+    fn dst_offset(&self, i: usize) -> Option<i32> {
+        let (offset, is_dst, _) = self.types[self.transitions[i].1];
+        if !is_dst {
+            return None;
+        }
+        // Type 0 is in effect before the first transition.
+        let before = match i {
+            0 => Some(0),
+            _ => self.transitions.get(i - 1).map(|&(_, index)| index),
+        };
+        let after = self.transitions.get(i + 1).map(|&(_, index)| index);
+        [before, after]
+            .into_iter()
+            .flatten()
+            .map(|index| self.types[index])
+            .filter(|&(other_offset, is_dst, _)| !is_dst && other_offset != offset)
+            .map(|(other_offset, _, _)| Some(offset - other_offset))
+            .fold(None, smallest)
+    }
+}
+
+fn smallest(a: Option<i32>, b: Option<i32>) -> Option<i32> {
+    match (a, b) {
+        (Some(a), Some(b)) if b.abs() < a.abs() => Some(b),
+        (Some(a), _) => Some(a),
+        (None, b) => b,
+    }
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn header(&mut self) -> Result<Header, TzifError> {
+        if self.take(4)? != b"TZif" {
+            return Err(TzifError::InvalidData("not a TZif file"));
+        }
+        let version = match self.take(1)?[0] {
+            0 => 1,
+            version @ b'2'..=b'9' => version - b'0',
+            _ => return Err(TzifError::InvalidData("unknown version")),
+        };
+        self.take(15)?;
+        let header = Header {
+            version,
+            isutcnt: self.count()?,
+            isstdcnt: self.count()?,
+            leapcnt: self.count()?,
+            timecnt: self.count()?,
+            typecnt: self.count()?,
+            charcnt: self.count()?,
+        };
+        if header.typecnt == 0
+            || header.charcnt == 0
+            || (header.isutcnt != 0 && header.isutcnt != header.typecnt)
+            || (header.isstdcnt != 0 && header.isstdcnt != header.typecnt)
+        {
+            return Err(TzifError::InvalidData("inconsistent header"));
+        }
+        Ok(header)
+    }
+
+    fn block(&mut self, header: &Header, time_size: usize) -> Result<Block<'a>, TzifError> {
+        let times = self.take(header.timecnt.saturating_mul(time_size))?;
+        let indices = self.take(header.timecnt)?;
+        let types = self.take(header.typecnt.saturating_mul(6))?;
+        let names = self.take(header.charcnt)?;
+        self.take(header.leapcnt.saturating_mul(time_size + 4))?;
+        self.take(header.isstdcnt.saturating_add(header.isutcnt))?;
+        if header.leapcnt != 0 {
+            return Err(TzifError::InvalidData("leap seconds are not supported"));
+        }
+
+        let mut transitions = Vec::with_capacity(header.timecnt);
+        for (time, &index) in times.chunks(time_size).zip(indices) {
+            let time = match time_size {
+                4 => i32::from_be_bytes(time.try_into().unwrap()) as i64,
+                _ => i64::from_be_bytes(time.try_into().unwrap()),
+            };
+            if (index as usize) >= header.typecnt {
+                return Err(TzifError::InvalidData("unknown local time type"));
+            }
+            if matches!(transitions.last(), Some(&(last, _)) if last >= time) {
+                return Err(TzifError::InvalidData("transitions out of order"));
+            }
+            transitions.push((time, index as usize));
+        }
+
+        let types = types
+            .chunks(6)
+            .map(|local_type| {
+                let offset = i32::from_be_bytes(local_type[..4].try_into().unwrap());
+                let is_dst = match local_type[4] {
+                    0 => false,
+                    1 => true,
+                    _ => return Err(TzifError::InvalidData("invalid daylight saving flag")),
+                };
+                let name = names
+                    .get(local_type[5] as usize..)
+                    .and_then(|name| {
+                        let len = name.iter().position(|&c| c == 0)?;
+                        std::str::from_utf8(&name[..len]).ok()
+                    })
+                    .ok_or(TzifError::InvalidData("invalid abbreviation"))?;
+                Ok((offset, is_dst, name))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Block { transitions, types })
+    }
+
+    fn footer(&mut self) -> Result<Option<ExtraRule<'a>>, TzifError> {
+        let invalid = TzifError::InvalidData("invalid footer");
+        if self.take(1)? != b"\n" {
+            return Err(invalid);
+        }
+        let len = self.0.iter().position(|&c| c == b'\n').ok_or(invalid)?;
+        let footer = self.take(len)?;
+        match footer {
+            b"" => Ok(None),
+            _ => std::str::from_utf8(footer)
+                .ok()
+                .and_then(posix::parse)
+                .map(Some)
+                .ok_or(TzifError::InvalidData("invalid TZ string in footer")),
+        }
+    }
+
+    fn count(&mut self) -> Result<usize, TzifError> {
+        let count = u32::from_be_bytes(self.take(4)?.try_into().unwrap());
+        Ok(count as usize)
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], TzifError> {
+        if self.0.len() < len {
+            return Err(TzifError::InvalidData("unexpected end of data"));
+        }
+        let (taken, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(taken)
+    }
+}
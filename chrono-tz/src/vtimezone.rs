@@ -0,0 +1,900 @@
+//! iCalendar `VTIMEZONE` components, as defined by RFC 5545 section 3.6.5: writing them
+//! for a [`Tz`], and reading them into a [`DynTz`].
+
+use core::fmt::Write;
+use core::ops::Range;
+
+use core::cmp::Reverse;
+use core::fmt::{self, Display, Formatter};
+
+use chrono::{
+    DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Utc, Weekday,
+};
+
+use crate::timezone_impl::{
+    ceil_timestamp, DaylightRule, ExtraRule, FixedTimespan, FixedTimespanSet, RuleDate,
+    RuleTransition, TimeSpans,
+};
+use crate::timezones::{Tz, TZ_VARIANTS};
+use crate::tzif::DynTz;
+
+/// One `STANDARD` or `DAYLIGHT` sub-component.
+struct Observance {
+    from: FixedTimespan<'static>,
+    to: FixedTimespan<'static>,
+    /// The UTC timestamps of the onsets; the first one is `DTSTART`.
+    onsets: Vec<i64>,
+    rrule: Option<String>,
+}
+
+impl Observance {
+    fn is_daylight(&self) -> bool {
+        // Zones with negative DST, such as Europe/Dublin, have none in summer.
+        self.to.dst_offset > 0 || (self.to.dst_offset == 0 && self.from.dst_offset < 0)
+    }
+
+    fn write(&self, out: &mut String) {
+        let kind = match self.is_daylight() {
+            true => "DAYLIGHT",
+            false => "STANDARD",
+        };
+        let _ = write!(out, "BEGIN:{kind}\r\n");
+        // Onsets are given in the local time before the change.
+        let local = |at: i64| local_time(at + self.from.total_offset());
+        let _ = write!(out, "DTSTART:{}\r\n", local(self.onsets[0]));
+        let _ = write!(out, "TZOFFSETFROM:{}\r\n", utc_offset(self.from));
+        let _ = write!(out, "TZOFFSETTO:{}\r\n", utc_offset(self.to));
+        let _ = write!(out, "TZNAME:{}\r\n", self.to);
+        match &self.rrule {
+            Some(rrule) => {
+                let _ = write!(out, "RRULE:{rrule}\r\n");
+            }
+            None => {
+                for &at in &self.onsets[1..] {
+                    let _ = write!(out, "RDATE:{}\r\n", local(at));
+                }
+            }
+        }
+        let _ = write!(out, "END:{kind}\r\n");
+    }
+}
+
+/// A local time in the form of an iCalendar `DATE-TIME`, such as `20240331T020000`.
+fn local_time(timestamp: i64) -> String {
+    let dt = DateTime::from_timestamp(timestamp, 0).unwrap_or_default();
+    format!(
+        "{:04}{:02}{:02}T{:02}{:02}{:02}",
+        dt.year(),
+        dt.month(),
+        dt.day(),
+        dt.hour(),
+        dt.minute(),
+        dt.second()
+    )
+}
+
+/// An offset in the form of an iCalendar `UTC-OFFSET`, such as `+0100`.
+fn utc_offset(timespan: FixedTimespan) -> String {
+    let offset = timespan.total_offset();
+    let sign = if offset < 0 { '-' } else { '+' };
+    let offset = offset.abs();
+    let mut out = format!("{sign}{:02}{:02}", offset / 3600, offset / 60 % 60);
+    if offset % 60 != 0 {
+        let _ = write!(out, "{:02}", offset % 60);
+    }
+    out
+}
+
+/// The `RRULE` for a yearly transition, if iCalendar can express it.
+fn rrule(transition: RuleTransition) -> Option<String> {
+    // An RRULE repeats the local time of DTSTART, which has to fall on the day itself.
+    if !(0..86_400).contains(&transition.time) {
+        return None;
+    }
+    Some(match transition.date {
+        RuleDate::MonthWeekday {
+            month,
+            week,
+            weekday,
+        } => {
+            let week = if week == 5 { -1 } else { week as i8 };
+            let weekday = ["SU", "MO", "TU", "WE", "TH", "FR", "SA"].get(weekday as usize)?;
+            format!("FREQ=YEARLY;BYMONTH={month};BYDAY={week}{weekday}")
+        }
+        RuleDate::JulianDay(day) => {
+            // Julian days never count February 29th, so any common year will do.
+            let date = NaiveDate::from_yo_opt(2001, day as u32)?;
+            format!(
+                "FREQ=YEARLY;BYMONTH={};BYMONTHDAY={}",
+                date.month(),
+                date.day()
+            )
+        }
+        RuleDate::DayOfYear(day) => format!("FREQ=YEARLY;BYYEARDAY={}", day + 1),
+    })
+}
+
+/// Whether `transition` is one that `rule` produces, with the same timespans, and the
+/// rule's next transition is the one at `next`, if there is one.
+fn follows_rule(
+    rule: &ExtraRule,
+    &(at, before, after): &(i64, FixedTimespan, FixedTimespan),
+    next: Option<i64>,
+) -> bool {
+    rule.timespan_at(at) == after
+        && rule.timespan_at(at - 1) == before
+        && next.map_or(true, |next| {
+            rule.next_transition(at).map(|(at, _, _)| at) == Some(next)
+        })
+}
+
+impl Tz {
+    /// Describes this timezone over `range` as an iCalendar `VTIMEZONE` component,
+    /// as calendar files and CalDAV servers expect next to events in the zone.
+    ///
+    /// The component has a `STANDARD` or `DAYLIGHT` observance for each offset that
+    /// is in effect during `range`, starting with the one at `range.start`. The
+    /// transitions are those of [`Tz::transitions_between()`]; once the zone keeps
+    /// to the recurring rule that `chrono-tz-build` takes from the last rules of its
+    /// zoneinfo entry, they are given as a yearly `RRULE`, and as lists of `RDATE`s
+    /// before that. An `RRULE` continues after `range.end`. Lines end with CRLF.
+    ///
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// use chrono_tz::Europe::Berlin;
+    ///
+    /// let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    /// let end = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+    /// let vtimezone = Berlin.to_vtimezone(start..end);
+    /// assert!(vtimezone.starts_with("BEGIN:VTIMEZONE\r\nTZID:Europe/Berlin\r\n"));
+    /// assert!(vtimezone.contains("RRULE:FREQ=YEARLY;BYMONTH=3;BYDAY=-1SU\r\n"));
+    /// ```
+    pub fn to_vtimezone(&self, range: Range<DateTime<Utc>>) -> String {
+        let timespans = self.timespans();
+        let start = ceil_timestamp(range.start);
+        let end = ceil_timestamp(range.end);
+
+        // The transition to the offset in effect at the start, followed by those in
+        // the range.
+        let mut transitions = Vec::new();
+        let mut next = timespans.previous_transition(start + 1);
+        let initial = match next {
+            Some(_) => None,
+            None => Some(timespans.timespan_at_utc(start)),
+        };
+        if next.is_none() {
+            next = timespans.next_transition(start - 1);
+        }
+        while let Some(transition) = next.filter(|&(at, _, _)| at < end) {
+            transitions.push(transition);
+            next = timespans.next_transition(transition.0);
+        }
+
+        // The transitions from `recurring` onwards all follow the zone's yearly rule.
+        let rules = timespans.extra.and_then(|rule| {
+            let daylight = rule.daylight?;
+            Some((rule, rrule(daylight.start)?, rrule(daylight.end)?))
+        });
+        let recurring = match rules {
+            Some((rule, _, _)) => (0..transitions.len())
+                .rev()
+                .find(|&index| {
+                    let next = transitions.get(index + 1).map(|&(at, _, _)| at);
+                    !follows_rule(&rule, &transitions[index], next)
+                })
+                .map_or(0, |index| index + 1),
+            None => transitions.len(),
+        };
+
+        let mut observances = Vec::<Observance>::new();
+        if let Some(timespan) = initial {
+            observances.push(Observance {
+                from: timespan,
+                to: timespan,
+                onsets: vec![start],
+                rrule: None,
+            });
+        }
+        for &(at, from, to) in &transitions[..recurring] {
+            let same = observances
+                .iter_mut()
+                .find(|observance| observance.from == from && observance.to == to);
+            match same {
+                Some(observance) => observance.onsets.push(at),
+                None => observances.push(Observance {
+                    from,
+                    to,
+                    onsets: vec![at],
+                    rrule: None,
+                }),
+            }
+        }
+        if let (Some((rule, start_rule, end_rule)), Some(&(first, _, _))) =
+            (rules, transitions.get(recurring))
+        {
+            // Both halves of the rule get a DTSTART, even if one of them only
+            // happens after the range.
+            let daylight = rule.daylight.unwrap();
+            for (timespan, rrule) in [(daylight.timespan, start_rule), (rule.standard, end_rule)] {
+                let mut onset = transitions[recurring..]
+                    .iter()
+                    .find(|&&(_, _, after)| after == timespan)
+                    .copied();
+                let mut at = first;
+                while onset.is_none() {
+                    let transition = rule.next_transition(at).unwrap();
+                    at = transition.0;
+                    onset = Some(transition).filter(|&(_, _, after)| after == timespan);
+                }
+                let (at, from, to) = onset.unwrap();
+                observances.push(Observance {
+                    from,
+                    to,
+                    onsets: vec![at],
+                    rrule: Some(rrule),
+                });
+            }
+        }
+        observances.sort_by_key(|observance| observance.onsets[0]);
+
+        let mut out = String::new();
+        let _ = write!(out, "BEGIN:VTIMEZONE\r\nTZID:{}\r\n", self.name());
+        for observance in &observances {
+            observance.write(&mut out);
+        }
+        out.push_str("END:VTIMEZONE\r\n");
+        out
+    }
+}
+
+impl DynTz {
+    /// Parses an iCalendar `VTIMEZONE` component, such as the ones that come with
+    /// invitations from other calendars.
+    ///
+    /// `vtimezone` runs from `BEGIN:VTIMEZONE` to `END:VTIMEZONE`, and the zone is
+    /// named after its `TZID`, which often isn't one of the IANA database. The
+    /// onsets of the `STANDARD` and `DAYLIGHT` observances are expanded up to the
+    /// end of 2099, like those of the compiled zones; when the last ones recur every
+    /// year in a way that a POSIX TZ string can express, they also continue after
+    /// that. `RRULE`s can only have a `FREQ` of `YEARLY`, and pick one day of one
+    /// month, as in `FREQ=YEARLY;BYMONTH=3;BYDAY=-1SU`.
+    ///
+    /// ```
+    /// use chrono::TimeZone;
+    /// use chrono_tz::{DynTz, OffsetName};
+    ///
+    /// let tz = DynTz::from_vtimezone(
+    ///     "BEGIN:VTIMEZONE\r\n\
+    ///      TZID:Custom Central European\r\n\
+    ///      BEGIN:STANDARD\r\n\
+    ///      DTSTART:19961027T030000\r\n\
+    ///      TZOFFSETFROM:+0200\r\n\
+    ///      TZOFFSETTO:+0100\r\n\
+    ///      TZNAME:CET\r\n\
+    ///      RRULE:FREQ=YEARLY;BYMONTH=10;BYDAY=-1SU\r\n\
+    ///      END:STANDARD\r\n\
+    ///      BEGIN:DAYLIGHT\r\n\
+    ///      DTSTART:19970330T020000\r\n\
+    ///      TZOFFSETFROM:+0100\r\n\
+    ///      TZOFFSETTO:+0200\r\n\
+    ///      TZNAME:CEST\r\n\
+    ///      RRULE:FREQ=YEARLY;BYMONTH=3;BYDAY=-1SU\r\n\
+    ///      END:DAYLIGHT\r\n\
+    ///      END:VTIMEZONE\r\n",
+    /// )
+    /// .unwrap();
+    /// let dt = tz.with_ymd_and_hms(2024, 7, 1, 12, 0, 0).unwrap();
+    /// assert_eq!(dt.to_string(), "2024-07-01 12:00:00 CEST");
+    /// assert_eq!(dt.offset().tz_id(), "Custom Central European");
+    /// ```
+    pub fn from_vtimezone(vtimezone: &str) -> Result<DynTz, VTimezoneError> {
+        let lines = unfold(vtimezone);
+        let mut lines = lines
+            .iter()
+            .filter(|line| !line.is_empty())
+            .map(|line| ContentLine::parse(line));
+
+        match lines.next().transpose()? {
+            Some(line) if line.is("BEGIN", "VTIMEZONE") => {}
+            _ => return Err(VTimezoneError::Syntax),
+        }
+        let mut tzid = None;
+        let mut components = Vec::new();
+        loop {
+            let line = lines.next().ok_or(VTimezoneError::Syntax)??;
+            if line.is("END", "VTIMEZONE") {
+                break;
+            } else if line.is("BEGIN", "STANDARD") || line.is("BEGIN", "DAYLIGHT") {
+                let daylight = line.value.eq_ignore_ascii_case("DAYLIGHT");
+                components.push(Component::parse(&mut lines, daylight)?);
+            } else if line.name.eq_ignore_ascii_case("BEGIN") {
+                skip(&mut lines, line.value)?;
+            } else if line.name.eq_ignore_ascii_case("TZID") {
+                tzid = Some(line.value);
+            }
+        }
+        if lines.next().is_some() {
+            return Err(VTimezoneError::Syntax);
+        }
+        let tzid = tzid.ok_or(VTimezoneError::MissingProperty("TZID"))?;
+        if components.is_empty() {
+            return Err(VTimezoneError::MissingProperty("STANDARD"));
+        }
+
+        let mut onsets = Vec::new();
+        for component in &components {
+            onsets.extend(component.onsets().into_iter().map(|at| (at, component)));
+        }
+        onsets.sort_by_key(|&(at, _)| at);
+
+        // Before the first onset, use the offset that it changes from.
+        let (_, earliest) = onsets[0];
+        let first = components
+            .iter()
+            .map(|component| component.timespan)
+            .find(|timespan| timespan.total_offset() == earliest.from as i64)
+            .unwrap_or(FixedTimespan {
+                utc_offset: earliest.from,
+                dst_offset: 0,
+                name: None,
+            });
+        let mut rest = Vec::<(i64, FixedTimespan)>::new();
+        for (at, component) in onsets {
+            let current = rest.last().map_or(first, |&(_, timespan)| timespan);
+            if component.timespan != current {
+                rest.push((at, component.timespan));
+            }
+        }
+
+        Ok(DynTz::from_timespans(
+            tzid,
+            first,
+            &rest,
+            extra_rule(&components),
+        ))
+    }
+
+    /// The compiled time zone that agrees best with this one during `range`, such as
+    /// `America/New_York` for a `VTIMEZONE` that describes Eastern Time.
+    ///
+    /// Every zone of [`TZ_VARIANTS`] is compared by how long it has the same offset
+    /// from UTC as this zone during `range`, and then by how long it also has the same
+    /// abbreviation. Ties go to a zone whose name or Windows ID is the name of this
+    /// zone, such as `W. Europe Standard Time` for `Europe/Berlin`, then to the zones
+    /// that represent a CLDR metazone, such as `America/New_York` for the other zones
+    /// on Eastern Time, and then to zones rather than links. Returns `None` if no zone
+    /// ever agrees.
+    ///
+    /// `range` should cover the events that use this zone, because zones that agree
+    /// today can differ in the past or the future.
+    ///
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// use chrono_tz::{DynTz, Tz};
+    ///
+    /// let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    /// let end = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+    /// let vtimezone = Tz::America__New_York.to_vtimezone(start..end);
+    /// let tz = DynTz::from_vtimezone(&vtimezone.replace("America/New_York", "Eastern")).unwrap();
+    /// assert_eq!(tz.closest_tz(start..end), Some(Tz::America__New_York));
+    /// ```
+    pub fn closest_tz(&self, range: Range<DateTime<Utc>>) -> Option<Tz> {
+        let start = range.start.timestamp();
+        let end = ceil_timestamp(range.end);
+        let timespans = self.timespans();
+        let named = self
+            .name()
+            .parse::<Tz>()
+            .ok()
+            .or_else(|| Tz::from_windows_id(self.name(), None))
+            .map(Tz::canonical);
+        TZ_VARIANTS
+            .iter()
+            .enumerate()
+            .map(|(index, &tz)| {
+                let (offsets, names) = agreement(&timespans, &tz.timespans(), start, end);
+                let key = (
+                    offsets,
+                    names,
+                    named == Some(tz.canonical()),
+                    tz.is_golden_zone(),
+                    !tz.is_link(),
+                    Reverse(index),
+                );
+                (key, tz)
+            })
+            .filter(|&((offsets, ..), _)| offsets > 0)
+            .max_by_key(|&(key, _)| key)
+            .map(|(_, tz)| tz)
+    }
+}
+
+/// How many seconds from `start` to `end` the two sets have the same total offset,
+/// and how many of those also the same abbreviation.
+fn agreement(a: &FixedTimespanSet, b: &FixedTimespanSet, start: i64, end: i64) -> (i64, i64) {
+    let (mut offsets, mut names) = (0, 0);
+    let mut at = start;
+    while at < end {
+        let next = [a.next_transition(at), b.next_transition(at)]
+            .into_iter()
+            .flatten()
+            .map(|(next, ..)| next.min(end))
+            .min()
+            .unwrap_or(end);
+        let (a_timespan, b_timespan) = (a.timespan_at_utc(at), b.timespan_at_utc(at));
+        if a_timespan.total_offset() == b_timespan.total_offset() {
+            offsets += next - at;
+            if a_timespan.name == b_timespan.name {
+                names += next - at;
+            }
+        }
+        at = next;
+    }
+    (offsets, names)
+}
+
+/// An error while parsing a `VTIMEZONE` component with [`DynTz::from_vtimezone`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum VTimezoneError {
+    /// The text is not a single `VTIMEZONE` component of well-formed content lines.
+    Syntax,
+    /// A required property or component is missing, such as `TZID` or `DTSTART`.
+    MissingProperty(&'static str),
+    /// A property has a value that isn't valid, such as an offset of `+2500`.
+    InvalidValue(&'static str),
+    /// An `RRULE` recurs in a way that is not supported.
+    UnsupportedRule,
+}
+
+impl Display for VTimezoneError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            VTimezoneError::Syntax => f.write_str("invalid VTIMEZONE component"),
+            VTimezoneError::MissingProperty(name) => write!(f, "missing {name} in VTIMEZONE"),
+            VTimezoneError::InvalidValue(name) => write!(f, "invalid {name} in VTIMEZONE"),
+            VTimezoneError::UnsupportedRule => f.write_str("unsupported RRULE in VTIMEZONE"),
+        }
+    }
+}
+
+impl std::error::Error for VTimezoneError {}
+
+/// Joins folded lines, which continue on the next line after a line break and a
+/// space or tab.
+fn unfold(text: &str) -> Vec<String> {
+    let mut lines = Vec::<String>::new();
+    for line in text.split('\n') {
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(continued), Some(last)) => last.push_str(continued),
+            _ => lines.push(line.into()),
+        }
+    }
+    lines
+}
+
+/// A property such as `DTSTART;TZID=Europe/Berlin:20240101T000000`.
+struct ContentLine<'a> {
+    name: &'a str,
+    params: &'a str,
+    value: &'a str,
+}
+
+impl<'a> ContentLine<'a> {
+    fn parse(line: &'a str) -> Result<Self, VTimezoneError> {
+        let name_end = line.find([';', ':']).ok_or(VTimezoneError::Syntax)?;
+        // Parameter values can be quoted to contain colons.
+        let mut quoted = false;
+        let colon = line[name_end..]
+            .find(|c| {
+                quoted ^= c == '"';
+                c == ':' && !quoted
+            })
+            .ok_or(VTimezoneError::Syntax)?;
+        Ok(ContentLine {
+            name: &line[..name_end],
+            params: &line[name_end..name_end + colon],
+            value: &line[name_end + colon + 1..],
+        })
+    }
+
+    fn is(&self, name: &str, value: &str) -> bool {
+        self.name.eq_ignore_ascii_case(name) && self.value.eq_ignore_ascii_case(value)
+    }
+
+    /// The value of the parameter `name`, such as `DATE` for `VALUE`.
+    fn param(&self, name: &str) -> Option<&'a str> {
+        self.params.split(';').find_map(|param| {
+            let (param, value) = param.split_once('=')?;
+            param.eq_ignore_ascii_case(name).then_some(value)
+        })
+    }
+}
+
+/// Skips the lines of a component that isn't used, up to its `END`.
+fn skip<'a>(
+    lines: &mut impl Iterator<Item = Result<ContentLine<'a>, VTimezoneError>>,
+    component: &str,
+) -> Result<(), VTimezoneError> {
+    loop {
+        let line = lines.next().ok_or(VTimezoneError::Syntax)??;
+        if line.is("END", component) {
+            return Ok(());
+        } else if line.name.eq_ignore_ascii_case("BEGIN") {
+            skip(lines, line.value)?;
+        }
+    }
+}
+
+/// A `STANDARD` or `DAYLIGHT` component, as read from a `VTIMEZONE`.
+struct Component<'a> {
+    daylight: bool,
+    /// The timespan in effect after each onset.
+    timespan: FixedTimespan<'a>,
+    /// The offset before each onset, in seconds.
+    from: i32,
+    /// The local time of the first onset.
+    start: NaiveDateTime,
+    recurrence: Option<Recurrence>,
+    /// The other onsets, in local time unless they are in UTC.
+    dates: Vec<(NaiveDateTime, bool)>,
+}
+
+impl<'a> Component<'a> {
+    fn parse(
+        lines: &mut impl Iterator<Item = Result<ContentLine<'a>, VTimezoneError>>,
+        daylight: bool,
+    ) -> Result<Self, VTimezoneError> {
+        let (mut start, mut from, mut to, mut name) = (None, None, None, None);
+        let mut recurrence = None;
+        let mut dates = Vec::new();
+        loop {
+            let line = lines.next().ok_or(VTimezoneError::Syntax)??;
+            let property = line.name.to_ascii_uppercase();
+            match &*property {
+                "END" => break,
+                "BEGIN" => skip(lines, line.value)?,
+                "DTSTART" => {
+                    let (dt, _) = date_time(line.value, line.param("VALUE"))
+                        .ok_or(VTimezoneError::InvalidValue("DTSTART"))?;
+                    start = Some(dt);
+                }
+                "TZOFFSETFROM" => {
+                    from = Some(utc_offset_value(line.value, "TZOFFSETFROM")?);
+                }
+                "TZOFFSETTO" => to = Some(utc_offset_value(line.value, "TZOFFSETTO")?),
+                "TZNAME" => name = name.or(Some(line.value)),
+                "RRULE" => recurrence = Some(line.value),
+                "RDATE" => {
+                    for value in line.value.split(',') {
+                        // A period's onset is its start.
+                        let value = value.split('/').next().unwrap_or(value);
+                        let date = date_time(value, line.param("VALUE"))
+                            .ok_or(VTimezoneError::InvalidValue("RDATE"))?;
+                        dates.push(date);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let start = start.ok_or(VTimezoneError::MissingProperty("DTSTART"))?;
+        let from = from.ok_or(VTimezoneError::MissingProperty("TZOFFSETFROM"))?;
+        let to = to.ok_or(VTimezoneError::MissingProperty("TZOFFSETTO"))?;
+        let recurrence = recurrence
+            .map(|rrule| Recurrence::parse(rrule, start))
+            .transpose()?;
+        // The standard offset of daylight saving time is the one it starts from.
+        let utc_offset = if daylight { from } else { to };
+        Ok(Component {
+            daylight,
+            timespan: FixedTimespan {
+                utc_offset,
+                dst_offset: to - utc_offset,
+                name: name.filter(|name| !name.is_empty()),
+            },
+            from,
+            start,
+            recurrence,
+            dates,
+        })
+    }
+
+    /// The UTC timestamps of the onsets, up to the end of 2099.
+    fn onsets(&self) -> Vec<i64> {
+        let utc = |(dt, is_utc): (NaiveDateTime, bool)| {
+            let timestamp = dt.and_utc().timestamp();
+            match is_utc {
+                true => timestamp,
+                false => timestamp - self.from as i64,
+            }
+        };
+        let mut onsets = vec![utc((self.start, false))];
+        onsets.extend(self.dates.iter().copied().map(utc));
+
+        if let Some(recurrence) = &self.recurrence {
+            let mut count = 1;
+            for year in self.start.year() + 1..=LAST_YEAR {
+                let onset = match recurrence.date(year) {
+                    Some(date) => date.and_time(self.start.time()),
+                    None => continue,
+                };
+                let ended = match recurrence.until {
+                    Some(until) => utc((onset, false)) > utc(until),
+                    None => false,
+                };
+                if ended || recurrence.count.map_or(false, |max| count >= max) {
+                    break;
+                }
+                onsets.push(utc((onset, false)));
+                count += 1;
+            }
+        }
+        onsets
+    }
+}
+
+/// Onsets are expanded up to the end of this year.
+const LAST_YEAR: i32 = 2099;
+
+/// The recurring rule that the components end with, if there is one.
+///
+/// That takes one component of daylight saving time and one of standard time that
+/// both recur without end, switching between each other.
+fn extra_rule<'a>(components: &[Component<'a>]) -> Option<ExtraRule<'a>> {
+    let mut endless = components.iter().filter(|component| {
+        component
+            .recurrence
+            .as_ref()
+            .map_or(false, |rule| rule.until.is_none() && rule.count.is_none())
+    });
+    let (a, b) = (endless.next()?, endless.next()?);
+    if endless.next().is_some() {
+        return None;
+    }
+    let (standard, daylight) = match (a.daylight, b.daylight) {
+        (false, true) => (a, b),
+        (true, false) => (b, a),
+        _ => return None,
+    };
+    if standard.from as i64 != daylight.timespan.total_offset()
+        || daylight.from as i64 != standard.timespan.total_offset()
+    {
+        return None;
+    }
+
+    let transition = |component: &Component| {
+        Some(RuleTransition {
+            date: component.recurrence.as_ref()?.rule_date()?,
+            time: component.start.time().num_seconds_from_midnight() as i32,
+        })
+    };
+    Some(ExtraRule {
+        standard: standard.timespan,
+        daylight: Some(DaylightRule {
+            timespan: daylight.timespan,
+            start: transition(daylight)?,
+            end: transition(standard)?,
+        }),
+    })
+}
+
+/// A yearly `RRULE`, which picks one day in one month.
+struct Recurrence {
+    month: u32,
+    day: RecurrenceDay,
+    /// The last onset, in local time unless it is in UTC.
+    until: Option<(NaiveDateTime, bool)>,
+    /// The number of onsets, including the first.
+    count: Option<u32>,
+}
+
+#[derive(Copy, Clone)]
+enum RecurrenceDay {
+    /// `BYMONTHDAY=d`
+    Day(u32),
+    /// `BYDAY=2SU` or `BYDAY=-1SU`
+    Weekday(i32, Weekday),
+    /// `BYDAY=SU;BYMONTHDAY=8,9,10,11,12,13,14`: the weekday on or after a day
+    WeekdayOnOrAfter(Weekday, u32),
+}
+
+impl Recurrence {
+    fn parse(rrule: &str, start: NaiveDateTime) -> Result<Self, VTimezoneError> {
+        let invalid = VTimezoneError::InvalidValue("RRULE");
+        let unsupported = VTimezoneError::UnsupportedRule;
+        let (mut month, mut weekday, mut days) = (None, None, None);
+        let (mut yearly, mut until, mut count) = (false, None, None);
+        for part in rrule.split(';') {
+            let (name, value) = part.split_once('=').ok_or(invalid)?;
+            match &*name.to_ascii_uppercase() {
+                "FREQ" if value.eq_ignore_ascii_case("YEARLY") => yearly = true,
+                "INTERVAL" if value == "1" => {}
+                "WKST" => {}
+                "BYMONTH" => month = Some(value.parse::<u32>().map_err(|_| unsupported)?),
+                "BYDAY" => weekday = Some(by_day(value).ok_or(unsupported)?),
+                "BYMONTHDAY" => {
+                    let parsed = value.split(',').map(|day| match day.parse::<i32>() {
+                        Ok(day @ 1..=31) => Ok(day as u32),
+                        // Days counted from the end of the month are valid but unsupported.
+                        Ok(-31..=-1) => Err(unsupported),
+                        _ => Err(invalid),
+                    });
+                    days = Some(parsed.collect::<Result<Vec<_>, _>>()?);
+                }
+                "UNTIL" => until = Some(date_time(value, None).ok_or(invalid)?),
+                "COUNT" => count = Some(value.parse().map_err(|_| invalid)?),
+                _ => return Err(unsupported),
+            }
+        }
+        if !yearly {
+            return Err(unsupported);
+        }
+
+        let month = month.unwrap_or(start.month());
+        let day = match (weekday, days.as_deref()) {
+            (None, None) => RecurrenceDay::Day(start.day()),
+            (None, Some(&[day])) => RecurrenceDay::Day(day),
+            (Some((Some(week), weekday)), None) => RecurrenceDay::Weekday(week, weekday),
+            (Some((None, weekday)), Some(days))
+                if days.len() == 7
+                    && days
+                        .windows(2)
+                        .all(|pair| pair[0].checked_add(1) == Some(pair[1])) =>
+            {
+                RecurrenceDay::WeekdayOnOrAfter(weekday, days[0])
+            }
+            _ => return Err(unsupported),
+        };
+        if !(1..=12).contains(&month) {
+            return Err(invalid);
+        }
+        Ok(Recurrence {
+            month,
+            day,
+            until,
+            count,
+        })
+    }
+
+    /// The day of the onset in `year`, if there is one.
+    fn date(&self, year: i32) -> Option<NaiveDate> {
+        match self.day {
+            RecurrenceDay::Day(day) => NaiveDate::from_ymd_opt(year, self.month, day),
+            RecurrenceDay::Weekday(week, weekday) if week > 0 => {
+                NaiveDate::from_weekday_of_month_opt(year, self.month, weekday, week as u8)
+            }
+            RecurrenceDay::Weekday(week, weekday) => {
+                let next_month = match self.month {
+                    12 => NaiveDate::from_ymd_opt(year + 1, 1, 1)?,
+                    month => NaiveDate::from_ymd_opt(year, month + 1, 1)?,
+                };
+                let last = next_month.pred_opt()?;
+                let back = (last.weekday().num_days_from_monday() + 7
+                    - weekday.num_days_from_monday())
+                    % 7;
+                let date = last - Duration::days(back as i64 - 7 * (week as i64 + 1));
+                (date.month() == self.month).then_some(date)
+            }
+            RecurrenceDay::WeekdayOnOrAfter(weekday, day) => {
+                let first = NaiveDate::from_ymd_opt(year, self.month, day)?;
+                let ahead = (weekday.num_days_from_monday() + 7
+                    - first.weekday().num_days_from_monday())
+                    % 7;
+                let date = first + Duration::days(ahead as i64);
+                (date.month() == self.month).then_some(date)
+            }
+        }
+    }
+
+    /// The day as a POSIX TZ string would give it, if it can.
+    fn rule_date(&self) -> Option<RuleDate> {
+        // The last week of February is a day longer in leap years.
+        let last_week = match self.month {
+            2 => None,
+            4 | 6 | 9 | 11 => Some(24),
+            _ => Some(25),
+        };
+        let week = match self.day {
+            RecurrenceDay::Day(day) => {
+                let date = NaiveDate::from_ymd_opt(2001, self.month, day)?;
+                return Some(RuleDate::JulianDay(date.ordinal() as u16));
+            }
+            RecurrenceDay::Weekday(-1, _) => 5,
+            RecurrenceDay::Weekday(week @ 1..=4, _) => week as u8,
+            RecurrenceDay::WeekdayOnOrAfter(_, day) if day % 7 == 1 && day <= 22 => {
+                (day / 7 + 1) as u8
+            }
+            RecurrenceDay::WeekdayOnOrAfter(_, day) if Some(day) == last_week => 5,
+            _ => return None,
+        };
+        let weekday = match self.day {
+            RecurrenceDay::Weekday(_, weekday) | RecurrenceDay::WeekdayOnOrAfter(weekday, _) => {
+                weekday.num_days_from_sunday() as u8
+            }
+            RecurrenceDay::Day(_) => unreachable!(),
+        };
+        Some(RuleDate::MonthWeekday {
+            month: self.month as u8,
+            week,
+            weekday,
+        })
+    }
+}
+
+/// Parses a `BYDAY` value with a single day, such as `SU`, `2SU` or `-1SU`.
+fn by_day(value: &str) -> Option<(Option<i32>, Weekday)> {
+    let split = value.len().checked_sub(2)?;
+    let weekday = match &*value.get(split..)?.to_ascii_uppercase() {
+        "MO" => Weekday::Mon,
+        "TU" => Weekday::Tue,
+        "WE" => Weekday::Wed,
+        "TH" => Weekday::Thu,
+        "FR" => Weekday::Fri,
+        "SA" => Weekday::Sat,
+        "SU" => Weekday::Sun,
+        _ => return None,
+    };
+    let week = match &value[..split] {
+        "" => None,
+        week => {
+            let week = week.strip_prefix('+').unwrap_or(week).parse().ok()?;
+            match week {
+                -5..=-1 | 1..=5 => Some(week),
+                _ => return None,
+            }
+        }
+    };
+    Some((week, weekday))
+}
+
+/// Parses a `DATE-TIME` such as `20240331T020000`, or a `DATE` if `value_type` says
+/// so, along with whether it is in UTC.
+fn date_time(value: &str, value_type: Option<&str>) -> Option<(NaiveDateTime, bool)> {
+    let (value, is_utc) = match value.strip_suffix(['Z', 'z']) {
+        Some(value) => (value, true),
+        None => (value, false),
+    };
+    let number = |range: Range<usize>| -> Option<u32> {
+        let digits = value.get(range)?;
+        match digits.bytes().all(|b| b.is_ascii_digit()) {
+            true => digits.parse().ok(),
+            false => None,
+        }
+    };
+    let date = NaiveDate::from_ymd_opt(number(0..4)? as i32, number(4..6)?, number(6..8)?)?;
+    if value.len() == 8 && value_type.map_or(true, |value| value.eq_ignore_ascii_case("DATE")) {
+        return Some((date.and_time(NaiveTime::MIN), is_utc));
+    }
+    if value.len() != 15 || !value[8..].starts_with(['T', 't']) {
+        return None;
+    }
+    // A leap second is read as the second before it.
+    let second = number(13..15)?.min(59);
+    let time = NaiveTime::from_hms_opt(number(9..11)?, number(11..13)?, second)?;
+    Some((date.and_time(time), is_utc))
+}
+
+/// Parses a `UTC-OFFSET` such as `+0100` or `-034530`.
+fn utc_offset_value(value: &str, property: &'static str) -> Result<i32, VTimezoneError> {
+    let invalid = VTimezoneError::InvalidValue(property);
+    let (sign, digits) = match value.as_bytes().first() {
+        Some(b'+') => (1, &value[1..]),
+        Some(b'-') => (-1, &value[1..]),
+        _ => return Err(invalid),
+    };
+    if !(digits.len() == 4 || digits.len() == 6) || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(invalid);
+    }
+    // The digits were checked above, and the seconds are optional.
+    let part = |range: Range<usize>| {
+        digits
+            .get(range)
+            .map_or(0, |part| part.parse().unwrap_or(0))
+    };
+    let (hours, minutes, seconds): (i32, i32, i32) = (part(0..2), part(2..4), part(4..6));
+    if hours > 23 || minutes > 59 || seconds > 59 {
+        return Err(invalid);
+    }
+    Ok(sign * (hours * 3600 + minutes * 60 + seconds))
+}
@@ -0,0 +1,41 @@
+//! Windows time zone IDs, from the `windowsZones.xml` file of the Unicode CLDR that
+//! is vendored in `cldr/`.
+
+use crate::timezones::{Tz, WINDOWS_IDS, WINDOWS_ZONES};
+
+impl Tz {
+    /// Looks up the timezone for a Windows time zone ID, such as `Pacific Standard Time`,
+    /// as sent by Exchange and Outlook.
+    ///
+    /// A Windows ID usually covers several zones; `territory` is the ISO 3166 code of
+    /// the country to pick one for. Without a territory, or for a territory that the
+    /// ID doesn't list, this returns the zone that CLDR gives as the default.
+    ///
+    /// ```
+    /// use chrono_tz::Tz;
+    ///
+    /// let id = "Pacific Standard Time";
+    /// assert_eq!(Tz::from_windows_id(id, None), Some(Tz::America__Los_Angeles));
+    /// assert_eq!(Tz::from_windows_id(id, Some("CA")), Some(Tz::America__Vancouver));
+    /// assert_eq!(Tz::from_windows_id("Mars Standard Time", None), None);
+    /// ```
+    pub fn from_windows_id(id: &str, territory: Option<&str>) -> Option<Tz> {
+        let zones = WINDOWS_ZONES.get(id)?;
+        let zone = territory.and_then(|territory| {
+            zones
+                .iter()
+                .find(|&&(other, _)| other == territory)
+                .map(|&(_, zone)| zone)
+        });
+        zone.or_else(|| zones.first().map(|&(_, zone)| zone))
+    }
+
+    /// The Windows time zone ID for this timezone, such as `Pacific Standard Time` for
+    /// `America/Los_Angeles`, or `None` if Windows doesn't have one.
+    ///
+    /// Links have the ID of the zone that they link to, unless CLDR lists them
+    /// separately.
+    pub fn windows_id(self) -> Option<&'static str> {
+        WINDOWS_IDS.get(self.name()).copied()
+    }
+}
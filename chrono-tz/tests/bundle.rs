@@ -0,0 +1,110 @@
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use std::thread;
+
+use chrono::{Duration, Offset, TimeZone, Utc};
+use chrono_tz::runtime::{BundleError, Database, SharedDatabase};
+use chrono_tz::{zones_for_country, OffsetName, Tz, IANA_TZDB_VERSION, TZ_VARIANTS};
+
+fn bundle(name: &str, countries: bool) -> Vec<u8> {
+    let path = Path::new(env!("CARGO_TARGET_TMPDIR")).join(name);
+    chrono_tz_build::write_bundle(&path, false, countries);
+    fs::read(path).unwrap()
+}
+
+#[test]
+fn matches_compiled_zones() {
+    let database = Database::from_bytes(&bundle("tzdb.bin", true)).unwrap();
+    assert_eq!(database.version(), IANA_TZDB_VERSION);
+    assert_eq!(database.names().count(), TZ_VARIANTS.len());
+
+    let start = Utc.with_ymd_and_hms(1900, 1, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2150, 1, 1, 0, 0, 0).unwrap();
+    for tz in TZ_VARIANTS {
+        let zone = database.get(tz.name()).unwrap();
+        assert_eq!(zone.name(), tz.name());
+        assert_eq!(database.canonical(tz.name()), Some(tz.canonical().name()));
+
+        for transition in tz.transitions_between(start, end) {
+            for at in [transition.at - Duration::seconds(1), transition.at] {
+                let expected = at.with_timezone(&tz);
+                let actual = at.with_timezone(&zone);
+                assert_eq!(actual.offset().fix(), expected.offset().fix(), "{tz} {at}");
+                assert_eq!(
+                    actual.offset().abbreviation(),
+                    expected.offset().abbreviation(),
+                    "{tz} {at}"
+                );
+            }
+        }
+    }
+    assert!(database.get("Mars/Olympus_Mons").is_none());
+}
+
+#[test]
+fn countries() {
+    let database = Database::from_bytes(&bundle("tzdb-countries.bin", true)).unwrap();
+    let germany = database.country("DE").unwrap();
+    assert_eq!(germany.name, "Germany");
+    let zones = zones_for_country("DE").iter().map(|tz| tz.name());
+    assert!(germany.zones.iter().map(String::as_str).eq(zones));
+    assert!(database.country("XX").is_none());
+
+    let database = Database::from_bytes(&bundle("tzdb-no-countries.bin", false)).unwrap();
+    assert!(database.countries().is_empty());
+    assert!(database.get("Europe/Berlin").is_some());
+}
+
+#[test]
+fn invalid_bundles() {
+    let data = bundle("tzdb-invalid.bin", false);
+    assert!(matches!(
+        Database::from_bytes(&data[..data.len() - 1]),
+        Err(BundleError::InvalidData(_))
+    ));
+    assert!(matches!(
+        Database::from_bytes(b"TZif2"),
+        Err(BundleError::InvalidData("not a tzdb bundle"))
+    ));
+
+    // Break the TZif magic of the first zone, which follows the header and its name.
+    let mut broken = data.clone();
+    let version_len = broken[5] as usize;
+    let name_start = 6 + version_len + 4;
+    let name_len = broken[name_start] as usize;
+    let name = String::from_utf8(broken[name_start + 1..][..name_len].to_vec()).unwrap();
+    broken[name_start + 1 + name_len + 4] = b'X';
+    match Database::from_bytes(&broken) {
+        Err(BundleError::InvalidZone { name: zone, .. }) => assert_eq!(zone, name),
+        other => panic!("{other:?}"),
+    }
+}
+
+#[test]
+fn replace_while_in_use() {
+    let data = bundle("tzdb-shared.bin", false);
+    let shared = Arc::new(SharedDatabase::new(Database::from_bytes(&data).unwrap()));
+    let london = shared.get("Europe/London").unwrap();
+
+    let readers = (0..4)
+        .map(|_| {
+            let shared = Arc::clone(&shared);
+            thread::spawn(move || {
+                for _ in 0..100 {
+                    assert_eq!(shared.get("Europe/Paris").unwrap().name(), "Europe/Paris");
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    let old = shared.replace(Database::from_bytes(&data).unwrap());
+    for reader in readers {
+        reader.join().unwrap();
+    }
+
+    assert!(!Arc::ptr_eq(&old, &shared.current()));
+    // Zones handed out before keep working.
+    let dt = london.with_ymd_and_hms(2016, 5, 10, 12, 0, 0).unwrap();
+    assert_eq!(dt.to_string(), "2016-05-10 12:00:00 BST");
+    assert_eq!(Tz::Europe__London.name(), london.name());
+}
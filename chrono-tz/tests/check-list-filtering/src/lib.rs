@@ -0,0 +1,45 @@
+/// This test is compiled by the Github workflows with the zone list set thusly:
+/// CHRONO_TZ_TIMEZONE_LIST="$PWD/zones.txt"
+///
+/// We use it to check that the compiled chrono-tz contains the listed zones along
+/// with the zones linked to them, and nothing else.
+
+#[cfg(test)]
+mod tests {
+    use chrono::offset::TimeZone;
+    use chrono_tz::{America, Europe, Tz, US, TZ_VARIANTS};
+    use std::str::FromStr;
+
+    #[test]
+    fn listed_zones_compile() {
+        // These lines will be a compilation failure if the code generation
+        // mistakenly excluded the zones.
+        let _london_time = Europe::London.with_ymd_and_hms(2013, 12, 25, 14, 0, 0);
+        let _new_york_time = America::New_York.with_ymd_and_hms(2013, 12, 25, 14, 0, 0);
+        assert_eq!(Tz::from_str("US/Pacific"), Ok(US::Pacific));
+
+        // Links to and from the listed zones are kept as well.
+        assert_eq!(Tz::from_str("Europe/Belfast"), Ok(Europe::Belfast));
+        assert_eq!(Tz::from_str("US/Eastern"), Ok(US::Eastern));
+        assert_eq!(
+            Tz::from_str("America/Los_Angeles"),
+            Ok(America::Los_Angeles)
+        );
+    }
+
+    #[test]
+    fn unlisted_zones_are_missing() {
+        assert!(Tz::from_str("Europe/Dublin").is_err());
+        assert!(Tz::from_str("Europe/Berlin").is_err());
+        assert!(Tz::from_str("America/Chicago").is_err());
+        assert!(Tz::from_str("US/Central").is_err());
+        assert!(Tz::from_str("Asia/Tokyo").is_err());
+
+        for tz in TZ_VARIANTS.iter() {
+            assert!(!tz.name().starts_with("Africa"));
+            assert!(!tz.name().starts_with("Asia"));
+            assert!(!tz.name().starts_with("Australia"));
+            assert!(!tz.name().starts_with("Pacific"));
+        }
+    }
+}
@@ -0,0 +1,78 @@
+/// This test is compiled by the Github workflows with the year range set thusly:
+/// CHRONO_TZ_YEARS="2000..2040"
+///
+/// We use it to check that the transitions within the range are kept, and that
+/// outside of it the zones keep the timespan in effect at the nearest end.
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Offset, TimeZone, Utc};
+    use chrono_tz::{America, Asia, Europe, OffsetName, Tz};
+
+    fn offset_at(tz: Tz, year: i32, month: u32) -> (i32, String) {
+        let utc = Utc.with_ymd_and_hms(year, month, 1, 12, 0, 0).unwrap();
+        let offset = tz.offset_from_utc_datetime(&utc.naive_utc());
+        let abbreviation = offset.abbreviation().unwrap().to_owned();
+        (offset.fix().local_minus_utc(), abbreviation)
+    }
+
+    #[test]
+    fn transitions_in_range() {
+        assert_eq!(offset_at(Europe::Berlin, 2025, 1), (3600, "CET".to_owned()));
+        assert_eq!(
+            offset_at(Europe::Berlin, 2025, 7),
+            (7200, "CEST".to_owned())
+        );
+        assert_eq!(
+            offset_at(Europe::Berlin, 2039, 7),
+            (7200, "CEST".to_owned())
+        );
+        assert_eq!(
+            offset_at(America::New_York, 2000, 7),
+            (-14400, "EDT".to_owned())
+        );
+    }
+
+    #[test]
+    fn clamped_before_range() {
+        // Berlin had summer time in 1990, and local mean time in 1850, but the data
+        // starts with the winter of 2000.
+        assert_eq!(offset_at(Europe::Berlin, 1990, 7), (3600, "CET".to_owned()));
+        assert_eq!(offset_at(Europe::Berlin, 1850, 7), (3600, "CET".to_owned()));
+        assert_eq!(
+            offset_at(America::New_York, 1850, 7),
+            (-18000, "EST".to_owned())
+        );
+        assert_eq!(offset_at(Asia::Kolkata, 1940, 1), (19800, "IST".to_owned()));
+    }
+
+    #[test]
+    fn clamped_after_range() {
+        // The data ends with the winter of 2039, without the rule for summer time.
+        assert_eq!(offset_at(Europe::Berlin, 2040, 7), (3600, "CET".to_owned()));
+        assert_eq!(
+            offset_at(America::New_York, 2050, 7),
+            (-18000, "EST".to_owned())
+        );
+
+        // Which is still known as a POSIX TZ string.
+        assert_eq!(
+            Europe::Berlin.posix_tz_string(),
+            Some("CET-1CEST,M3.5.0,M10.5.0/3")
+        );
+    }
+
+    #[test]
+    fn shared_timespans_keep_names() {
+        // Within the range, Berlin and Paris have the same timespans, so they share
+        // the generated tables, but remain different zones.
+        assert_eq!(
+            offset_at(Europe::Paris, 2025, 7),
+            offset_at(Europe::Berlin, 2025, 7)
+        );
+        assert_ne!(Europe::Paris, Europe::Berlin);
+        assert_eq!(Europe::Paris.name(), "Europe/Paris");
+        assert_eq!(Europe::Berlin.name(), "Europe/Berlin");
+        assert_eq!("Europe/Paris".parse::<Tz>(), Ok(Europe::Paris));
+    }
+}
@@ -0,0 +1,139 @@
+use std::fs;
+
+use chrono::{DateTime, Duration, Offset, TimeZone, Utc};
+use chrono_tz::{DynTz, OffsetName, Tz, TzifError, IANA_TZDB_VERSION};
+
+const ZONEINFO: &str = "/usr/share/zoneinfo";
+
+// Not every distribution ships `+VERSION`, but `tzdata.zi` starts with the version too.
+fn system_version() -> Option<String> {
+    if let Ok(version) = fs::read_to_string(format!("{ZONEINFO}/+VERSION")) {
+        return Some(version.trim().to_owned());
+    }
+    let tzdata = fs::read_to_string(format!("{ZONEINFO}/tzdata.zi")).ok()?;
+    let version = tzdata.lines().next()?.strip_prefix("# version ")?;
+    Some(version.trim().to_owned())
+}
+
+#[test]
+fn matches_compiled_zones() {
+    if system_version().as_deref() != Some(IANA_TZDB_VERSION) {
+        return;
+    }
+
+    // Distributions may build with `backzone`, which changes links and data before 1970,
+    // so only compare the zones in `zone1970.tab` since 1970.
+    let zone1970 = fs::read_to_string(format!("{ZONEINFO}/zone1970.tab")).unwrap();
+    let zones = zone1970
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| line.split('\t').nth(2));
+    let start = Utc.with_ymd_and_hms(1970, 1, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2150, 1, 1, 0, 0, 0).unwrap();
+    for name in zones {
+        let tz: Tz = name.parse().unwrap();
+        let dyn_tz = DynTz::from_zoneinfo(ZONEINFO, name).unwrap();
+        assert_eq!(dyn_tz.name(), name);
+
+        // Check both sides of every transition, as UTC and as local times.
+        for transition in tz.transitions_between(start, end) {
+            for at in [transition.at - Duration::seconds(1), transition.at] {
+                let expected = at.with_timezone(&tz);
+                let actual = at.with_timezone(&dyn_tz);
+                assert_eq!(actual.offset().fix(), expected.offset().fix(), "{tz} {at}");
+                assert_eq!(
+                    actual.offset().abbreviation(),
+                    expected.offset().abbreviation(),
+                    "{tz} {at}"
+                );
+
+                let local = expected.naive_local();
+                assert_eq!(
+                    dyn_tz
+                        .from_local_datetime(&local)
+                        .map(|dt| dt.with_timezone(&Utc)),
+                    tz.from_local_datetime(&local)
+                        .map(|dt| dt.with_timezone(&Utc)),
+                    "{tz} {local}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn dst_offset_components() {
+    let Ok(london) = DynTz::from_zoneinfo(ZONEINFO, "Europe/London") else {
+        return;
+    };
+    use chrono_tz::OffsetComponents;
+
+    let summer = london.with_ymd_and_hms(2016, 5, 10, 12, 0, 0).unwrap();
+    assert_eq!(summer.to_string(), "2016-05-10 12:00:00 BST");
+    assert_eq!(summer.offset().base_utc_offset(), Duration::hours(0));
+    assert_eq!(summer.offset().dst_offset(), Duration::hours(1));
+    assert_eq!(summer.offset().tz_id(), "Europe/London");
+
+    // After the transitions in the file, the rule from the footer takes over.
+    let far_future = london.with_ymd_and_hms(2200, 7, 1, 12, 0, 0).unwrap();
+    assert_eq!(far_future.offset().abbreviation(), Some("BST"));
+    assert_eq!(far_future.offset().dst_offset(), Duration::hours(1));
+}
+
+#[test]
+fn invalid_names() {
+    for name in [
+        "",
+        "../etc/passwd",
+        "/etc/passwd",
+        "Europe/../../etc/passwd",
+        "./UTC",
+    ] {
+        assert!(matches!(
+            DynTz::from_zoneinfo(ZONEINFO, name),
+            Err(TzifError::InvalidName)
+        ));
+    }
+}
+
+#[test]
+fn invalid_data() {
+    for data in [
+        &b""[..],
+        b"TZif",
+        b"not a TZif file",
+        b"TZif2\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0",
+    ] {
+        assert!(matches!(
+            DynTz::from_tzif("Invalid", data),
+            Err(TzifError::InvalidData(_))
+        ));
+    }
+}
+
+#[test]
+fn footer_only() {
+    let mut data = Vec::new();
+    for _ in 0..2 {
+        data.extend_from_slice(b"TZif2");
+        data.extend_from_slice(&[0; 15]);
+        // One local time type, with a 4 byte abbreviation.
+        for count in [0u32, 0, 0, 0, 1, 4] {
+            data.extend_from_slice(&count.to_be_bytes());
+        }
+        data.extend_from_slice(&(-5 * 3600i32).to_be_bytes());
+        data.extend_from_slice(&[0, 0]);
+        data.extend_from_slice(b"EST\0");
+    }
+    data.extend_from_slice(b"\nEST5EDT,M3.2.0,M11.1.0\n");
+
+    let tz = DynTz::from_tzif("EST5EDT", &data).unwrap();
+    let winter = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+    assert_eq!(
+        winter.with_timezone(&tz).offset().abbreviation(),
+        Some("EST")
+    );
+    let summer = tz.with_ymd_and_hms(2024, 7, 1, 12, 0, 0).unwrap();
+    assert_eq!(summer.offset().abbreviation(), Some("EDT"));
+    assert_eq!(summer.to_rfc3339(), "2024-07-01T12:00:00-04:00");
+}
@@ -0,0 +1,183 @@
+use chrono::{Duration, LocalResult, NaiveDate, Offset, TimeZone, Utc};
+use chrono_tz::{OffsetComponents, OffsetName, PosixTz, Tz, TZ_VARIANTS};
+
+fn parse(tz_string: &str) -> PosixTz {
+    tz_string.parse().unwrap()
+}
+
+#[test]
+fn matches_compiled_zones() {
+    for (tz_string, tz) in [
+        ("EST5EDT,M3.2.0,M11.1.0", Tz::America__New_York),
+        // Without dates, the rules of the United States apply.
+        ("EST5EDT", Tz::America__New_York),
+        ("CET-1CEST,M3.5.0,M10.5.0/3", Tz::Europe__Berlin),
+        ("IST-1GMT0,M10.5.0,M3.5.0/1", Tz::Europe__Dublin),
+        ("AEST-10AEDT,M10.1.0,M4.1.0/3", Tz::Australia__Sydney),
+        (
+            "<+1030>-10:30<+11>-11,M10.1.0,M4.1.0",
+            Tz::Australia__Lord_Howe,
+        ),
+        ("<-02>2<-01>,M3.5.0/-1,M10.5.0/0", Tz::America__Nuuk),
+    ] {
+        let posix_tz = parse(tz_string);
+        let start = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2200, 1, 1, 0, 0, 0).unwrap();
+        for transition in tz.transitions_between(start, end) {
+            for at in [transition.at - Duration::seconds(1), transition.at] {
+                let expected = at.with_timezone(&tz);
+                let actual = at.with_timezone(&posix_tz);
+                assert_eq!(actual.offset().fix(), expected.offset().fix(), "{tz} {at}");
+                assert_eq!(
+                    actual.offset().abbreviation(),
+                    expected.offset().abbreviation(),
+                    "{tz} {at}"
+                );
+                assert_eq!(
+                    actual.offset().dst_offset(),
+                    expected.offset().dst_offset(),
+                    "{tz} {at}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn tz_strings_of_compiled_zones() {
+    let start = Utc.with_ymd_and_hms(2150, 1, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2152, 1, 1, 0, 0, 0).unwrap();
+    for tz in TZ_VARIANTS {
+        let Some(tz_string) = tz.posix_tz_string() else {
+            continue;
+        };
+        let posix_tz = parse(tz_string);
+        for at in [start, end] {
+            assert_eq!(
+                at.with_timezone(&posix_tz).offset().fix(),
+                at.with_timezone(&tz).offset().fix(),
+                "{tz} {at}"
+            );
+        }
+        for transition in tz.transitions_between(start, end) {
+            let at = transition.at;
+            let actual = at.with_timezone(&posix_tz);
+            assert_eq!(actual.offset().fix(), transition.after.fix(), "{tz} {at}");
+            let actual = (at - Duration::seconds(1)).with_timezone(&posix_tz);
+            assert_eq!(actual.offset().fix(), transition.before.fix(), "{tz} {at}");
+        }
+    }
+}
+
+#[test]
+fn gaps_and_folds() {
+    let tz = parse("CET-1CEST,M3.5.0,M10.5.0/3");
+    let local = |month, day, hour| {
+        NaiveDate::from_ymd_opt(2024, month, day)
+            .unwrap()
+            .and_hms_opt(hour, 30, 0)
+            .unwrap()
+    };
+    assert_eq!(tz.from_local_datetime(&local(3, 31, 2)), LocalResult::None);
+    let LocalResult::Ambiguous(earlier, later) = tz.from_local_datetime(&local(10, 27, 2)) else {
+        panic!("expected a repeated local time");
+    };
+    assert_eq!(earlier.to_string(), "2024-10-27 02:30:00 CEST");
+    assert_eq!(later.to_string(), "2024-10-27 02:30:00 CET");
+    assert_eq!(later - earlier, Duration::hours(1));
+}
+
+#[test]
+fn day_of_year_forms() {
+    let start_of_dst = |tz: &PosixTz, year| {
+        let mut at = Utc.with_ymd_and_hms(year, 1, 1, 0, 0, 0).unwrap();
+        while at.with_timezone(tz).offset().dst_offset() == Duration::zero() {
+            at += Duration::hours(1);
+        }
+        at.with_timezone(tz).naive_local()
+    };
+    let ymd_h = |year, month, day, hour| {
+        NaiveDate::from_ymd_opt(year, month, day)
+            .unwrap()
+            .and_hms_opt(hour, 0, 0)
+            .unwrap()
+    };
+
+    // `Jn` never counts February 29th, so day 60 is always March 1st.
+    let julian = parse("AAA0BBB,J60,J300");
+    assert_eq!(start_of_dst(&julian, 2023), ymd_h(2023, 3, 1, 3));
+    assert_eq!(start_of_dst(&julian, 2024), ymd_h(2024, 3, 1, 3));
+
+    // `n` is zero-based and counts February 29th.
+    let zero_based = parse("AAA0BBB,59,300");
+    assert_eq!(start_of_dst(&zero_based, 2023), ymd_h(2023, 3, 1, 3));
+    assert_eq!(start_of_dst(&zero_based, 2024), ymd_h(2024, 2, 29, 3));
+
+    // Week 5 is the last such weekday, even in months with only four of them.
+    let last_sunday = parse("AAA0BBB,M2.5.0/12,M10.5.0");
+    assert_eq!(start_of_dst(&last_sunday, 2026), ymd_h(2026, 2, 22, 13));
+}
+
+#[test]
+fn extended_transition_times() {
+    // Thursday of the fourth week, plus 50 hours: the Saturday after it.
+    let gaza = parse("EET-2EEST,M3.4.4/50,M10.4.4/50");
+    let before = gaza.with_ymd_and_hms(2024, 3, 30, 1, 59, 59).unwrap();
+    assert_eq!(before.offset().abbreviation(), Some("EET"));
+    let after = before + Duration::seconds(1);
+    assert_eq!(after.to_string(), "2024-03-30 03:00:00 EEST");
+
+    // All year daylight saving time, as `zic` writes it.
+    let permanent = parse("EST5EDT,0/0,J365/25");
+    for month in 1..=12 {
+        let dt = Utc.with_ymd_and_hms(2024, month, 1, 0, 0, 0).unwrap();
+        assert_eq!(
+            dt.with_timezone(&permanent).offset().abbreviation(),
+            Some("EDT")
+        );
+    }
+}
+
+#[test]
+fn offsets_and_names() {
+    let tz = parse("<+0330>-3:30");
+    let dt = tz.with_ymd_and_hms(2024, 7, 1, 12, 0, 0).unwrap();
+    assert_eq!(dt.to_rfc3339(), "2024-07-01T12:00:00+03:30");
+    assert_eq!(dt.offset().abbreviation(), Some("+0330"));
+    assert_eq!(dt.offset().tz_id(), "<+0330>-3:30");
+    assert_eq!(tz.to_string(), "<+0330>-3:30");
+
+    // Without an offset, daylight saving time is an hour ahead.
+    let tz = parse("NZST-12NZDT,M9.5.0,M4.1.0/3");
+    let dt = tz.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap();
+    assert_eq!(dt.offset().base_utc_offset(), Duration::hours(12));
+    assert_eq!(dt.offset().dst_offset(), Duration::hours(1));
+
+    let tz = parse("HST10");
+    let dt = tz.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap();
+    assert_eq!(dt.to_rfc3339(), "2024-01-01T12:00:00-10:00");
+}
+
+#[test]
+fn invalid_tz_strings() {
+    for tz_string in [
+        "",
+        "EST",
+        "ES5",
+        "EST5 ",
+        "EST24",
+        "<+03-3",
+        "<+3>-3",
+        "EST5EDT,M3.2.0",
+        "EST5EDT,M13.2.0,M11.1.0",
+        "EST5EDT,M3.6.0,M11.1.0",
+        "EST5EDT,M3.2.7,M11.1.0",
+        "EST5EDT,J0,J365",
+        "EST5EDT,366,J365",
+        "EST5EDT,M3.2.0/168,M11.1.0",
+        "EST5EDT,M3.2.0/2:60,M11.1.0",
+        ":America/New_York",
+    ] {
+        assert!(tz_string.parse::<PosixTz>().is_err(), "{tz_string}");
+    }
+}
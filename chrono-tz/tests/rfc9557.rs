@@ -0,0 +1,214 @@
+use chrono::{TimeZone, Utc};
+use chrono_tz::{format_rfc9557, parse_rfc9557, OffsetConflict, Rfc9557Error, Tz};
+
+fn parse(input: &str) -> Result<String, Rfc9557Error> {
+    parse_rfc9557(input, OffsetConflict::Reject).map(|dt| format_rfc9557(&dt).to_string())
+}
+
+#[test]
+fn round_trips() {
+    for input in [
+        "2025-03-30T03:30:00+02:00[Europe/Paris]",
+        "2024-11-03T01:30:00-04:00[America/New_York]",
+        "2024-11-03T01:30:00-05:00[America/New_York]",
+        "2024-07-01T12:00:00.123+05:30[Asia/Kolkata]",
+        "2024-07-01T12:00:00.000001+00:00[UTC]",
+        "2024-07-01T12:00:00.123456789+14:00[Etc/GMT-14]",
+        "1890-01-01T00:00:00+00:09[Europe/Paris]",
+        "+012345-01-01T00:00:00+01:00[Europe/Berlin]",
+    ] {
+        assert_eq!(parse(input).as_deref(), Ok(input));
+    }
+}
+
+#[test]
+fn lenient_forms() {
+    for (input, expected) in [
+        (
+            "2025-03-30t03:30:00,5+02:00[Europe/Paris]",
+            "2025-03-30T03:30:00.500+02:00[Europe/Paris]",
+        ),
+        (
+            "2025-03-30 03:30:00+02:00[Europe/Paris]",
+            "2025-03-30T03:30:00+02:00[Europe/Paris]",
+        ),
+        // `Z` only gives the instant, so there is no conflict to resolve.
+        (
+            "2025-01-15T12:00:00Z[Europe/Paris]",
+            "2025-01-15T13:00:00+01:00[Europe/Paris]",
+        ),
+        // Leap seconds are read as the second before them.
+        (
+            "2016-12-31T23:59:60Z[UTC]",
+            "2016-12-31T23:59:59+00:00[UTC]",
+        ),
+        // Offsets of local mean time are written rounded to minutes.
+        (
+            "1890-01-01T00:00:00+00:09:21[Europe/Paris]",
+            "1890-01-01T00:00:00+00:09[Europe/Paris]",
+        ),
+        (
+            "1913-06-01T00:00:00-03:06:28[America/Sao_Paulo]",
+            "1913-06-01T00:00:00-03:06[America/Sao_Paulo]",
+        ),
+        // Links keep their own name.
+        (
+            "2024-07-01T12:00:00-07:00[US/Pacific]",
+            "2024-07-01T12:00:00-07:00[US/Pacific]",
+        ),
+        // Tags are ignored unless they are critical.
+        (
+            "2024-07-01T12:00:00+02:00[!Europe/Paris][u-ca=hebrew][x-foo=bar-baz]",
+            "2024-07-01T12:00:00+02:00[Europe/Paris]",
+        ),
+        (
+            "2024-07-01T12:00:00+02:00[Europe/Paris][!u-ca=iso8601]",
+            "2024-07-01T12:00:00+02:00[Europe/Paris]",
+        ),
+    ] {
+        assert_eq!(parse(input).as_deref(), Ok(expected), "{input}");
+    }
+}
+
+#[test]
+fn offset_conflicts() {
+    let input = "2025-01-15T12:00:00+02:00[Europe/Paris]";
+    let paris = Tz::Europe__Paris;
+    assert_eq!(
+        parse_rfc9557(input, OffsetConflict::UseOffset),
+        Ok(Utc
+            .with_ymd_and_hms(2025, 1, 15, 10, 0, 0)
+            .unwrap()
+            .with_timezone(&paris))
+    );
+    assert_eq!(
+        parse_rfc9557(input, OffsetConflict::UseZone),
+        Ok(paris.with_ymd_and_hms(2025, 1, 15, 12, 0, 0).unwrap())
+    );
+    assert_eq!(
+        parse_rfc9557(input, OffsetConflict::Reject),
+        Err(Rfc9557Error::OffsetConflict)
+    );
+
+    // A critical time zone rejects conflicts whatever the policy.
+    let input = "2025-01-15T12:00:00+02:00[!Europe/Paris]";
+    for conflict in [OffsetConflict::UseOffset, OffsetConflict::UseZone] {
+        assert_eq!(
+            parse_rfc9557(input, conflict),
+            Err(Rfc9557Error::OffsetConflict)
+        );
+    }
+
+    // An offset without seconds matches the offset of local mean time that it rounds,
+    // but an offset with seconds has to match exactly.
+    let lmt = Tz::Europe__Paris
+        .with_ymd_and_hms(1890, 1, 1, 0, 0, 0)
+        .unwrap();
+    assert_eq!(
+        parse_rfc9557(
+            "1890-01-01T00:00:00+00:09[!Europe/Paris]",
+            OffsetConflict::UseOffset
+        ),
+        Ok(lmt)
+    );
+    assert_eq!(
+        parse_rfc9557(
+            "1890-01-01T00:00:00+00:09:20[Europe/Paris]",
+            OffsetConflict::Reject
+        ),
+        Err(Rfc9557Error::OffsetConflict)
+    );
+    assert_eq!(
+        parse_rfc9557(
+            "1890-01-01T00:00:00+00:10[Europe/Paris]",
+            OffsetConflict::Reject
+        ),
+        Err(Rfc9557Error::OffsetConflict)
+    );
+
+    // The local time doesn't exist, so the zone moves it forward.
+    let skipped = "2025-03-30T02:30:00+01:00[Europe/Paris]";
+    assert_eq!(
+        parse_rfc9557(skipped, OffsetConflict::UseZone).map(|dt| dt.to_rfc3339()),
+        Ok("2025-03-30T03:30:00+02:00".to_string())
+    );
+    assert_eq!(
+        parse_rfc9557(skipped, OffsetConflict::UseOffset).map(|dt| dt.to_rfc3339()),
+        Ok("2025-03-30T03:30:00+02:00".to_string())
+    );
+}
+
+#[test]
+fn invalid_timestamps() {
+    for (input, error) in [
+        ("2025-03-30T03:30:00+02:00", Rfc9557Error::MissingTimeZone),
+        (
+            "2025-03-30T03:30:00+02:00[Europe/Lutetia]",
+            Rfc9557Error::UnknownTimeZone,
+        ),
+        (
+            "2025-03-30T03:30:00+02:00[+02:00]",
+            Rfc9557Error::UnknownTimeZone,
+        ),
+        (
+            "2025-03-30T03:30:00+02:00[europe/paris]",
+            Rfc9557Error::UnknownTimeZone,
+        ),
+        (
+            "2025-03-30T03:30:00+02:00[Europe/Paris][!u-ca=hebrew]",
+            Rfc9557Error::UnsupportedCriticalTag,
+        ),
+        (
+            "2025-03-30T03:30:00+02:00[u-ca=iso8601][Europe/Paris]",
+            Rfc9557Error::Invalid,
+        ),
+        (
+            "2025-03-30T03:30:00+02:00[Europe/Paris][Europe/Berlin]",
+            Rfc9557Error::Invalid,
+        ),
+        (
+            "2025-03-30T03:30:00+02:00[Europe/Paris][U-CA=iso8601]",
+            Rfc9557Error::Invalid,
+        ),
+        (
+            "2025-03-30T03:30:00+02:00[Europe/Paris",
+            Rfc9557Error::Invalid,
+        ),
+        (
+            "2025-03-30T03:30:00+02:00[Europe/Paris]x",
+            Rfc9557Error::Invalid,
+        ),
+        (
+            "2025-02-30T03:30:00+01:00[Europe/Paris]",
+            Rfc9557Error::Invalid,
+        ),
+        (
+            "2025-03-30T24:00:00+02:00[Europe/Paris]",
+            Rfc9557Error::Invalid,
+        ),
+        (
+            "2025-03-30T03:30:61+02:00[Europe/Paris]",
+            Rfc9557Error::Invalid,
+        ),
+        (
+            "2025-03-30T03:30+02:00[Europe/Paris]",
+            Rfc9557Error::Invalid,
+        ),
+        (
+            "2025-03-30T03:30:00.+02:00[Europe/Paris]",
+            Rfc9557Error::Invalid,
+        ),
+        (
+            "2025-03-30T03:30:00+0200[Europe/Paris]",
+            Rfc9557Error::Invalid,
+        ),
+        (
+            "2025-03-30T03:30:00+24:00[Europe/Paris]",
+            Rfc9557Error::Invalid,
+        ),
+        ("-000000-01-01T00:00:00Z[UTC]", Rfc9557Error::Invalid),
+        ("", Rfc9557Error::Invalid),
+    ] {
+        assert_eq!(parse(input), Err(error), "{input}");
+    }
+}
@@ -0,0 +1,112 @@
+#![cfg(unix)]
+
+use std::fs;
+use std::os::unix::fs::symlink;
+use std::path::{Path, PathBuf};
+use std::sync::Once;
+
+use chrono_tz::{system_tz_with, SystemTzConfig, SystemTzError, Tz};
+
+/// A directory for the files of one test, next to a zoneinfo directory that they share.
+fn fixture(test: &str) -> (PathBuf, SystemTzConfig) {
+    static ZONEINFO: Once = Once::new();
+    let root = Path::new(env!("CARGO_TARGET_TMPDIR")).join("system_tz");
+    let zoneinfo = root.join("zoneinfo");
+    ZONEINFO.call_once(|| chrono_tz_build::write_tzif_files(&zoneinfo, false));
+
+    let etc = root.join(test);
+    let _ = fs::remove_dir_all(&etc);
+    fs::create_dir_all(&etc).unwrap();
+    let config = SystemTzConfig {
+        tz_var: None,
+        localtime: etc.join("localtime"),
+        timezone_file: etc.join("timezone"),
+        zoneinfo,
+    };
+    (etc, config)
+}
+
+#[test]
+fn tz_variable() {
+    let (_, mut config) = fixture("tz_variable");
+    let berlin = config.zoneinfo.join("Europe/Berlin");
+    for (tz_var, expected) in [
+        ("Europe/Berlin", Ok(Tz::Europe__Berlin)),
+        (":US/Pacific", Ok(Tz::US__Pacific)),
+        ("", Ok(Tz::UTC)),
+        (berlin.to_str().unwrap(), Ok(Tz::Europe__Berlin)),
+        // A TZ string that many zones have is taken as the one representing them.
+        ("EST5EDT,M3.2.0,M11.1.0", Ok(Tz::America__New_York)),
+        ("IST-5:30", Ok(Tz::Asia__Kolkata)),
+        ("<+0530>-5:30", Ok(Tz::Asia__Colombo)),
+        (
+            "Mars/Olympus_Mons",
+            Err(SystemTzError::UnknownTzVar("Mars/Olympus_Mons".to_owned())),
+        ),
+    ] {
+        config.tz_var = Some(tz_var.to_owned());
+        assert_eq!(system_tz_with(&config), expected, "{tz_var}");
+    }
+}
+
+#[test]
+fn localtime_links() {
+    let (etc, config) = fixture("localtime_links");
+    let zoneinfo = &config.zoneinfo;
+    let alternatives = etc.join("alternatives");
+    for (target, expected) in [
+        (zoneinfo.join("America/Sao_Paulo"), Tz::America__Sao_Paulo),
+        // Links to links keep the name of the first.
+        (zoneinfo.join("Asia/Calcutta"), Tz::Asia__Calcutta),
+        (
+            Path::new("../zoneinfo/Australia/Sydney").to_owned(),
+            Tz::Australia__Sydney,
+        ),
+        (alternatives.clone(), Tz::Pacific__Auckland),
+    ] {
+        let _ = fs::remove_file(&alternatives);
+        symlink(zoneinfo.join("Pacific/Auckland"), &alternatives).unwrap();
+        let _ = fs::remove_file(&config.localtime);
+        symlink(&target, &config.localtime).unwrap();
+        assert_eq!(system_tz_with(&config), Ok(expected), "{target:?}");
+    }
+}
+
+#[test]
+fn timezone_file() {
+    let (_, config) = fixture("timezone_file");
+    fs::write(&config.timezone_file, "America/Argentina/Buenos_Aires\n").unwrap();
+    assert_eq!(
+        system_tz_with(&config),
+        Ok(Tz::America__Argentina__Buenos_Aires)
+    );
+
+    // A link at /etc/localtime wins.
+    symlink(config.zoneinfo.join("Asia/Tokyo"), &config.localtime).unwrap();
+    assert_eq!(system_tz_with(&config), Ok(Tz::Asia__Tokyo));
+}
+
+#[test]
+fn localtime_copies() {
+    let (_, config) = fixture("localtime_copies");
+    for (name, expected) in [
+        ("Asia/Kolkata", Tz::Asia__Kolkata),
+        // Links are found as the zone they link to.
+        ("Asia/Calcutta", Tz::Asia__Kolkata),
+        ("Europe/Berlin", Tz::Europe__Berlin),
+        ("America/Chicago", Tz::America__Chicago),
+    ] {
+        fs::copy(config.zoneinfo.join(name), &config.localtime).unwrap();
+        assert_eq!(system_tz_with(&config), Ok(expected), "{name}");
+    }
+}
+
+#[test]
+fn not_found() {
+    let (_, config) = fixture("not_found");
+    assert_eq!(system_tz_with(&config), Err(SystemTzError::NotFound));
+
+    fs::write(&config.timezone_file, "Not/A_Zone\n").unwrap();
+    fs::write(&config.localtime, "not a TZif file").unwrap();
+    assert_eq!(system_tz_with(&config), Err(SystemTzError::NotFound));
+}
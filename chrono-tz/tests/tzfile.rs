@@ -1,7 +1,8 @@
 use std::fs;
+use std::path::Path;
 
-use chrono::{DateTime, Offset, TimeZone};
-use chrono_tz::{IANA_TZDB_VERSION, TZ_VARIANTS};
+use chrono::{DateTime, Duration, Offset, TimeZone, Utc};
+use chrono_tz::{DynTz, OffsetName, IANA_TZDB_VERSION, TZ_VARIANTS};
 use tzfile::Tz;
 
 #[test]
@@ -36,3 +37,43 @@ fn tzfile() {
         }
     }
 }
+
+#[test]
+fn chrono_tz_build_output() {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("zoneinfo");
+    chrono_tz_build::write_tzif_files(&dir, false);
+    assert_eq!(
+        fs::read_to_string(dir.join("+VERSION")).unwrap().trim(),
+        IANA_TZDB_VERSION
+    );
+
+    let start = Utc.with_ymd_and_hms(1800, 1, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2200, 1, 1, 0, 0, 0).unwrap();
+    // Files only hold transitions up to 2100, then their footer takes over.
+    let footer_start = Utc.with_ymd_and_hms(2100, 1, 1, 0, 0, 0).unwrap();
+    for tz in TZ_VARIANTS {
+        let data = fs::read(dir.join(tz.name())).unwrap();
+        let file = Tz::parse(tz.name(), &data).unwrap();
+        let dyn_tz = DynTz::from_tzif(tz.name(), &data).unwrap();
+
+        for transition in tz.transitions_between(start, end) {
+            for at in [transition.at - Duration::seconds(1), transition.at] {
+                let expected = at.with_timezone(&tz);
+                let actual = at.with_timezone(&dyn_tz);
+                assert_eq!(actual.offset().fix(), expected.offset().fix(), "{tz} {at}");
+                assert_eq!(
+                    actual.offset().abbreviation(),
+                    expected.offset().abbreviation(),
+                    "{tz} {at}"
+                );
+                if at < footer_start {
+                    assert_eq!(
+                        at.with_timezone(&&file).offset().fix(),
+                        expected.offset().fix(),
+                        "{tz} {at}"
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,348 @@
+use chrono::{DateTime, Duration, Offset, TimeZone, Utc};
+use chrono_tz::{DynTz, OffsetComponents, OffsetName, Tz, VTimezoneError};
+
+fn vtimezone(tz: Tz, start_year: i32, end_year: i32) -> String {
+    let start = Utc.with_ymd_and_hms(start_year, 1, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(end_year, 1, 1, 0, 0, 0).unwrap();
+    tz.to_vtimezone(start..end).replace("\r\n", "\n")
+}
+
+#[test]
+fn recurring_rules() {
+    assert_eq!(
+        vtimezone(Tz::Europe__Berlin, 2024, 2026),
+        "\
+BEGIN:VTIMEZONE
+TZID:Europe/Berlin
+BEGIN:STANDARD
+DTSTART:20231029T030000
+TZOFFSETFROM:+0200
+TZOFFSETTO:+0100
+TZNAME:CET
+RRULE:FREQ=YEARLY;BYMONTH=10;BYDAY=-1SU
+END:STANDARD
+BEGIN:DAYLIGHT
+DTSTART:20240331T020000
+TZOFFSETFROM:+0100
+TZOFFSETTO:+0200
+TZNAME:CEST
+RRULE:FREQ=YEARLY;BYMONTH=3;BYDAY=-1SU
+END:DAYLIGHT
+END:VTIMEZONE
+"
+    );
+}
+
+#[test]
+fn historical_transitions() {
+    // Iran abolished daylight saving time in 2022.
+    assert_eq!(
+        vtimezone(Tz::Asia__Tehran, 2020, 2024),
+        "\
+BEGIN:VTIMEZONE
+TZID:Asia/Tehran
+BEGIN:STANDARD
+DTSTART:20190922T000000
+TZOFFSETFROM:+0430
+TZOFFSETTO:+0330
+TZNAME:+0330
+RDATE:20200921T000000
+RDATE:20210922T000000
+RDATE:20220922T000000
+END:STANDARD
+BEGIN:DAYLIGHT
+DTSTART:20200321T000000
+TZOFFSETFROM:+0330
+TZOFFSETTO:+0430
+TZNAME:+0430
+RDATE:20210322T000000
+RDATE:20220322T000000
+END:DAYLIGHT
+END:VTIMEZONE
+"
+    );
+}
+
+#[test]
+fn before_first_transition() {
+    assert_eq!(
+        vtimezone(Tz::Europe__Berlin, 1800, 1900),
+        "\
+BEGIN:VTIMEZONE
+TZID:Europe/Berlin
+BEGIN:STANDARD
+DTSTART:18000101T005328
+TZOFFSETFROM:+005328
+TZOFFSETTO:+005328
+TZNAME:LMT
+END:STANDARD
+BEGIN:STANDARD
+DTSTART:18930401T000000
+TZOFFSETFROM:+005328
+TZOFFSETTO:+0100
+TZNAME:CET
+END:STANDARD
+END:VTIMEZONE
+"
+    );
+}
+
+#[test]
+fn rules_outside_the_day() {
+    // Greenland changes its clocks at -1:00 local time, which an RRULE can't express.
+    let vtimezone = vtimezone(Tz::America__Nuuk, 2026, 2028);
+    assert!(!vtimezone.contains("RRULE"));
+    assert!(vtimezone.contains("DTSTART:20260328T230000\n"));
+    assert!(vtimezone.contains("RDATE:20270327T230000\n"));
+}
+
+#[test]
+fn fixed_offsets() {
+    assert_eq!(
+        vtimezone(Tz::Etc__GMTPlus5, 2024, 2025),
+        "\
+BEGIN:VTIMEZONE
+TZID:Etc/GMT+5
+BEGIN:STANDARD
+DTSTART:20231231T190000
+TZOFFSETFROM:-0500
+TZOFFSETTO:-0500
+TZNAME:-05
+END:STANDARD
+END:VTIMEZONE
+"
+    );
+}
+
+/// Asserts that `dyn_tz` has the offsets of `tz` around each of its transitions from
+/// `start` to `end`.
+fn assert_agrees(dyn_tz: &DynTz, tz: Tz, start: DateTime<Utc>, end: DateTime<Utc>) {
+    for transition in tz.transitions_between(start, end) {
+        for at in [transition.at - Duration::seconds(1), transition.at] {
+            let expected = at.with_timezone(&tz);
+            let actual = at.with_timezone(dyn_tz);
+            assert_eq!(actual.offset().fix(), expected.offset().fix(), "{tz} {at}");
+            assert_eq!(actual.offset().to_string(), expected.offset().to_string());
+        }
+    }
+}
+
+#[test]
+fn round_trips() {
+    let start = Utc.with_ymd_and_hms(1990, 1, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2040, 1, 1, 0, 0, 0).unwrap();
+    for tz in [
+        Tz::Europe__Berlin,
+        Tz::Europe__Dublin,
+        Tz::Asia__Tehran,
+        Tz::America__Sao_Paulo,
+        // Haiti skipped daylight saving time in 2016.
+        Tz::America__PortauPrince,
+        Tz::America__Nuuk,
+        Tz::Australia__Lord_Howe,
+    ] {
+        let dyn_tz = DynTz::from_vtimezone(&tz.to_vtimezone(start..end)).unwrap();
+        assert_eq!(dyn_tz.name(), tz.name());
+        assert_agrees(&dyn_tz, tz, start, end);
+        assert_eq!(dyn_tz.closest_tz(start..end), Some(tz));
+    }
+}
+
+// The example of RFC 5545, with the rules of New York since 1967.
+const NEW_YORK: &str = "\
+BEGIN:VTIMEZONE
+TZID:America/New_York
+LAST-MODIFIED:20050809T050000Z
+BEGIN:DAYLIGHT
+DTSTART:19670430T020000
+RRULE:FREQ=YEARLY;BYMONTH=4;BYDAY=-1SU;UNTIL=19730429T070000Z
+TZOFFSETFROM:-0500
+TZOFFSETTO:-0400
+TZNAME:EDT
+END:DAYLIGHT
+BEGIN:STANDARD
+DTSTART:19671029T020000
+RRULE:FREQ=YEARLY;BYMONTH=10;BYDAY=-1SU;UNTIL=20061029T060000Z
+TZOFFSETFROM:-0400
+TZOFFSETTO:-0500
+TZNAME:EST
+END:STANDARD
+BEGIN:DAYLIGHT
+DTSTART:19740106T020000
+RDATE:19750223T020000
+TZOFFSETFROM:-0500
+TZOFFSETTO:-0400
+TZNAME:EDT
+END:DAYLIGHT
+BEGIN:DAYLIGHT
+DTSTART:19760425T020000
+RRULE:FREQ=YEARLY;BYMONTH=4;BYDAY=-1SU;UNTIL=19860427T070000Z
+TZOFFSETFROM:-0500
+TZOFFSETTO:-0400
+TZNAME:EDT
+END:DAYLIGHT
+BEGIN:DAYLIGHT
+DTSTART:19870405T020000
+RRULE:FREQ=YEARLY;BYMONTH=4;BYDAY=1SU;UNTIL=20060402T070000Z
+TZOFFSETFROM:-0500
+TZOFFSETTO:-0400
+TZNAME:EDT
+END:DAYLIGHT
+BEGIN:DAYLIGHT
+DTSTART:20070311T020000
+RRULE:FREQ=YEARLY;BYMONTH=3;BYDAY=2SU
+TZOFFSETFROM:-0500
+TZOFFSETTO:-0400
+TZNAME:EDT
+END:DAYLIGHT
+BEGIN:STANDARD
+DTSTART:20071104T020000
+RRULE:FREQ=YEARLY;BYMONTH=11;BYDAY=1SU
+TZOFFSETFROM:-0400
+TZOFFSETTO:-0500
+TZNAME:EST
+END:STANDARD
+END:VTIMEZONE
+";
+
+#[test]
+fn rfc_5545_example() {
+    let tz = DynTz::from_vtimezone(NEW_YORK).unwrap();
+    let start = Utc.with_ymd_and_hms(1967, 1, 1, 0, 0, 0).unwrap();
+    // The rules continue after the onsets that are expanded.
+    let end = Utc.with_ymd_and_hms(2200, 1, 1, 0, 0, 0).unwrap();
+    assert_agrees(&tz, Tz::America__New_York, start, end);
+
+    let summer = tz.with_ymd_and_hms(2024, 7, 1, 12, 0, 0).unwrap();
+    assert_eq!(summer.offset().abbreviation(), Some("EDT"));
+    assert_eq!(summer.offset().base_utc_offset(), Duration::hours(-5));
+    assert_eq!(summer.offset().dst_offset(), Duration::hours(1));
+    assert_eq!(summer.offset().tz_id(), "America/New_York");
+}
+
+#[test]
+fn windows_zones() {
+    // As sent by Outlook, with a Windows ID, folded lines and properties of its own.
+    let tz = DynTz::from_vtimezone(
+        "BEGIN:VTIMEZONE\r
+TZID:W. Europe Standard Time\r
+X-MICROSOFT-CDO-TZID:4\r
+BEGIN:STANDARD\r
+DTSTART:16010101T030000\r
+TZOFFSETFROM:+0200\r
+TZOFFSETTO:+0100\r
+RRULE:FREQ=YEARLY;BYDAY=-1SU;BYMO\r
+ NTH=10\r
+END:STANDARD\r
+BEGIN:DAYLIGHT\r
+DTSTART:16010101T020000\r
+TZOFFSETFROM:+0100\r
+TZOFFSETTO:+0200\r
+RRULE:FREQ=YEARLY;BYDAY=-1SU;BYMONTH=3\r
+END:DAYLIGHT\r
+END:VTIMEZONE\r
+",
+    )
+    .unwrap();
+    let start = Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2030, 1, 1, 0, 0, 0).unwrap();
+    for transition in Tz::Europe__Berlin.transitions_between(start, end) {
+        let at = transition.at.with_timezone(&tz);
+        assert_eq!(at.offset().fix(), transition.after.fix());
+        assert_eq!(at.offset().abbreviation(), None);
+    }
+    assert_eq!(tz.closest_tz(start..end), Some(Tz::Europe__Berlin));
+}
+
+#[test]
+fn weekdays_on_or_after() {
+    // The second Sunday of March, as the weekday on or after the 8th.
+    let tz = DynTz::from_vtimezone(
+        "BEGIN:VTIMEZONE
+TZID:Eastern
+BEGIN:DAYLIGHT
+DTSTART:20070311T020000
+RRULE:FREQ=YEARLY;BYMONTH=3;BYDAY=SU;BYMONTHDAY=8,9,10,11,12,13,14
+TZOFFSETFROM:-0500
+TZOFFSETTO:-0400
+TZNAME:EDT
+END:DAYLIGHT
+BEGIN:STANDARD
+DTSTART:20071104T020000
+RRULE:FREQ=YEARLY;BYMONTH=11;BYDAY=SU;BYMONTHDAY=1,2,3,4,5,6,7
+TZOFFSETFROM:-0400
+TZOFFSETTO:-0500
+TZNAME:EST
+END:STANDARD
+END:VTIMEZONE
+",
+    )
+    .unwrap();
+    let start = Utc.with_ymd_and_hms(2008, 1, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2200, 1, 1, 0, 0, 0).unwrap();
+    assert_agrees(&tz, Tz::America__New_York, start, end);
+    assert_eq!(tz.closest_tz(start..end), Some(Tz::America__New_York));
+}
+
+#[test]
+fn invalid_vtimezones() {
+    let component = |lines: &str| format!("BEGIN:VTIMEZONE\nTZID:Test\n{lines}END:VTIMEZONE\n");
+    let standard = |lines: &str| {
+        component(&format!(
+            "BEGIN:STANDARD\nDTSTART:20000101T000000\nTZOFFSETFROM:+0100\n{lines}END:STANDARD\n"
+        ))
+    };
+    for (vtimezone, error) in [
+        ("".to_owned(), VTimezoneError::Syntax),
+        (
+            "BEGIN:VEVENT\nEND:VEVENT\n".to_owned(),
+            VTimezoneError::Syntax,
+        ),
+        (
+            "BEGIN:VTIMEZONE\nTZID:Test\n".to_owned(),
+            VTimezoneError::Syntax,
+        ),
+        (component(""), VTimezoneError::MissingProperty("STANDARD")),
+        (
+            standard("TZOFFSETTO:+0100\n").replace("TZID:Test\n", ""),
+            VTimezoneError::MissingProperty("TZID"),
+        ),
+        (standard(""), VTimezoneError::MissingProperty("TZOFFSETTO")),
+        (
+            standard("TZOFFSETTO:+2500\n"),
+            VTimezoneError::InvalidValue("TZOFFSETTO"),
+        ),
+        (
+            standard("TZOFFSETTO:+0100\nRDATE:20001301T000000\n"),
+            VTimezoneError::InvalidValue("RDATE"),
+        ),
+        (
+            standard("TZOFFSETTO:+0100\nRRULE:FREQ=MONTHLY;BYDAY=1SU\n"),
+            VTimezoneError::UnsupportedRule,
+        ),
+        (
+            standard("TZOFFSETTO:+0100\nRRULE:FREQ=YEARLY;BYMONTH=3,4;BYDAY=1SU\n"),
+            VTimezoneError::UnsupportedRule,
+        ),
+        (
+            standard("TZOFFSETTO:+0100\nRRULE:FREQ=YEARLY;BYDAY=SU;BYMONTHDAY=-1\n"),
+            VTimezoneError::UnsupportedRule,
+        ),
+        (
+            standard("TZOFFSETTO:+0100\nRRULE:FREQ=YEARLY;BYMONTHDAY=32\n"),
+            VTimezoneError::InvalidValue("RRULE"),
+        ),
+        (
+            standard(
+                "TZOFFSETTO:+0100\nRRULE:FREQ=YEARLY;BYDAY=SU;BYMONTHDAY=4294967295,0,1,2,3,4,5\n",
+            ),
+            VTimezoneError::InvalidValue("RRULE"),
+        ),
+    ] {
+        assert_eq!(
+            DynTz::from_vtimezone(&vtimezone).err(),
+            Some(error),
+            "{vtimezone}"
+        );
+    }
+}
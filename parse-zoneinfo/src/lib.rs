@@ -26,6 +26,9 @@
 //!   `table` module;
 //! - **Calculating transitions** from this table is done by the `transitions`
 //!   module.
+//!
+//! The `tab` module separately reads the tables of countries and their zones
+//! that come with the database.
 
 #![warn(missing_copy_implementations)]
 //#![warn(missing_docs)]
@@ -36,6 +39,7 @@
 
 pub mod line;
 pub mod structure;
+pub mod tab;
 pub mod table;
 pub mod transitions;
 
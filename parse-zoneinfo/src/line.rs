@@ -168,7 +168,7 @@ pub enum Month {
 }
 
 impl Month {
-    fn length(self, is_leap: bool) -> i8 {
+    pub(crate) fn length(self, is_leap: bool) -> i8 {
         match self {
             Month::January => 31,
             Month::February if is_leap => 29,
@@ -0,0 +1,296 @@
+//! Parsing the tables that describe where each zone is used.
+//!
+//! Besides the zoneinfo data files, the tz database ships three tab-separated
+//! tables meant to help users pick a time zone:
+//!
+//! - `iso3166.tab` maps ISO 3166 alpha-2 country codes to country names;
+//! - `zone.tab` has a row for every zone of every country, even when several
+//!   countries share the same clocks, with one country code per row;
+//! - `zone1970.tab` has a row for every zone whose clocks have agreed since 1970,
+//!   listing all the countries that it covers.
+//!
+//! Both zone tables have the same columns, so `ZoneTabEntry` parses rows of
+//! either of them.
+//!
+//! ## Examples
+//!
+//! ```
+//! use parse_zoneinfo::tab::*;
+//!
+//! let entry = ZoneTabEntry::new("DE\t+5230+01322\tEurope/Berlin\tmost of Germany");
+//! assert_eq!(entry, Ok(Some(ZoneTabEntry {
+//!     countries:   vec!["DE"],
+//!     coordinates: Coordinates { latitude: 52 * 3600 + 30 * 60, longitude: 13 * 3600 + 22 * 60 },
+//!     zone:        "Europe/Berlin",
+//!     comment:     Some("most of Germany"),
+//! })));
+//!
+//! let country = Country::new("DE\tGermany");
+//! assert_eq!(country, Ok(Some(Country { code: "DE", name: "Germany" })));
+//! ```
+
+use std::fmt;
+
+#[derive(PartialEq, Debug, Clone)]
+pub enum Error {
+    InvalidLine(String),
+    InvalidCountryCode(String),
+    InvalidCoordinates(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::InvalidLine(s) => write!(f, "line with invalid format: \"{}\"", s),
+            Error::InvalidCountryCode(s) => write!(f, "invalid country code: \"{}\"", s),
+            Error::InvalidCoordinates(s) => write!(f, "invalid coordinates: \"{}\"", s),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// A row of `iso3166.tab`.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct Country<'a> {
+    /// The ISO 3166 alpha-2 code of the country, such as `DE`.
+    pub code: &'a str,
+    /// The name of the country in English, such as `Germany`.
+    pub name: &'a str,
+}
+
+impl<'a> Country<'a> {
+    /// Attempt to parse a line of `iso3166.tab`, returning `None` for comments
+    /// and empty lines.
+    pub fn new(input: &'a str) -> Result<Option<Self>, Error> {
+        let mut columns = match columns(input) {
+            Some(columns) => columns,
+            None => return Ok(None),
+        };
+
+        let (code, name) = match (columns.next(), columns.next(), columns.next()) {
+            (Some(code), Some(name), None) => (code, name),
+            _ => return Err(Error::InvalidLine(input.to_string())),
+        };
+        Ok(Some(Country {
+            code: country_code(code)?,
+            name,
+        }))
+    }
+}
+
+/// A row of `zone.tab` or `zone1970.tab`.
+#[derive(PartialEq, Debug, Clone)]
+pub struct ZoneTabEntry<'a> {
+    /// The ISO 3166 alpha-2 codes of the countries that the zone covers. Rows of
+    /// `zone.tab` always have exactly one.
+    pub countries: Vec<&'a str>,
+    /// The location of the principal city of the zone.
+    pub coordinates: Coordinates,
+    /// The name of the zone, such as `Europe/Berlin`.
+    pub zone: &'a str,
+    /// A description of the area that the zone covers, such as `most of Germany`.
+    /// This is only present for countries with more than one zone.
+    pub comment: Option<&'a str>,
+}
+
+impl<'a> ZoneTabEntry<'a> {
+    /// Attempt to parse a line of `zone.tab` or `zone1970.tab`, returning `None`
+    /// for comments and empty lines.
+    pub fn new(input: &'a str) -> Result<Option<Self>, Error> {
+        let mut columns = match columns(input) {
+            Some(columns) => columns,
+            None => return Ok(None),
+        };
+
+        let (countries, coordinates, zone, comment) = match (
+            columns.next(),
+            columns.next(),
+            columns.next(),
+            columns.next(),
+            columns.next(),
+        ) {
+            (Some(countries), Some(coordinates), Some(zone), comment, None) => {
+                (countries, coordinates, zone, comment)
+            }
+            _ => return Err(Error::InvalidLine(input.to_string())),
+        };
+        if zone.is_empty() {
+            return Err(Error::InvalidLine(input.to_string()));
+        }
+
+        Ok(Some(ZoneTabEntry {
+            countries: countries
+                .split(',')
+                .map(country_code)
+                .collect::<Result<_, _>>()?,
+            coordinates: coordinates.parse()?,
+            zone,
+            comment,
+        }))
+    }
+}
+
+/// A location in ISO 6709 format, as it appears in `zone.tab` and `zone1970.tab`:
+/// `±DDMM±DDDMM` or `±DDMMSS±DDDMMSS`.
+///
+/// Both values are given in seconds of arc, so that they are exact.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct Coordinates {
+    /// The latitude, where positive values are north of the equator.
+    pub latitude: i32,
+    /// The longitude, where positive values are east of Greenwich.
+    pub longitude: i32,
+}
+
+impl std::str::FromStr for Coordinates {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::InvalidCoordinates(input.to_string());
+        let split = input
+            .get(1..)
+            .and_then(|rest| rest.find(['+', '-']))
+            .ok_or_else(invalid)?
+            + 1;
+        let (latitude, longitude) = input.split_at(split);
+        Ok(Coordinates {
+            latitude: angle(latitude, 2, 90).ok_or_else(invalid)?,
+            longitude: angle(longitude, 3, 180).ok_or_else(invalid)?,
+        })
+    }
+}
+
+/// Parses `±DDMM` or `±DDMMSS`, with `digits` digits of degrees, into seconds of arc.
+fn angle(input: &str, digits: usize, max_degrees: i32) -> Option<i32> {
+    let sign = match input.as_bytes().first()? {
+        b'+' => 1,
+        b'-' => -1,
+        _ => return None,
+    };
+    let input = &input[1..];
+    if !input.bytes().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    if input.len() != digits + 2 && input.len() != digits + 4 {
+        return None;
+    }
+
+    let degrees = input[..digits].parse::<i32>().ok()?;
+    let minutes = input[digits..digits + 2].parse::<i32>().ok()?;
+    let seconds = match input.get(digits + 2..) {
+        Some("") | None => 0,
+        Some(seconds) => seconds.parse::<i32>().ok()?,
+    };
+    if minutes >= 60 || seconds >= 60 {
+        return None;
+    }
+
+    let angle = degrees * 3600 + minutes * 60 + seconds;
+    match angle <= max_degrees * 3600 {
+        true => Some(sign * angle),
+        false => None,
+    }
+}
+
+/// The tab-separated columns of a line, or `None` for comments and empty lines.
+fn columns(input: &str) -> Option<std::str::Split<'_, char>> {
+    match input.starts_with('#') || input.trim().is_empty() {
+        true => None,
+        false => Some(input.split('\t')),
+    }
+}
+
+fn country_code(input: &str) -> Result<&str, Error> {
+    match input.len() == 2 && input.bytes().all(|c| c.is_ascii_uppercase()) {
+        true => Ok(input),
+        false => Err(Error::InvalidCountryCode(input.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn comments() {
+        assert_eq!(Country::new("# ISO 3166 alpha-2 country codes"), Ok(None));
+        assert_eq!(Country::new(""), Ok(None));
+        assert_eq!(
+            ZoneTabEntry::new("#codes\tcoordinates\tTZ\tcomments"),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn countries() {
+        assert_eq!(
+            Country::new("BA\tBosnia & Herzegovina"),
+            Ok(Some(Country {
+                code: "BA",
+                name: "Bosnia & Herzegovina",
+            }))
+        );
+        assert_eq!(
+            Country::new("de\tGermany"),
+            Err(Error::InvalidCountryCode("de".to_string()))
+        );
+        assert_eq!(
+            Country::new("DE Germany"),
+            Err(Error::InvalidLine("DE Germany".to_string()))
+        );
+    }
+
+    #[test]
+    fn zone1970_entries() {
+        assert_eq!(
+            ZoneTabEntry::new("CH,DE,LI\t+4723+00832\tEurope/Zurich\tBüsingen"),
+            Ok(Some(ZoneTabEntry {
+                countries: vec!["CH", "DE", "LI"],
+                coordinates: Coordinates {
+                    latitude: 47 * 3600 + 23 * 60,
+                    longitude: 8 * 3600 + 32 * 60,
+                },
+                zone: "Europe/Zurich",
+                comment: Some("Büsingen"),
+            }))
+        );
+        assert_eq!(
+            ZoneTabEntry::new("AD\t+4230+00131\tEurope/Andorra").map(|e| e.unwrap().comment),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn coordinates() {
+        assert_eq!(
+            "+404251-0740023".parse(),
+            Ok(Coordinates {
+                latitude: 40 * 3600 + 42 * 60 + 51,
+                longitude: -(74 * 3600 + 23),
+            })
+        );
+        assert_eq!(
+            "-3352+15113".parse(),
+            Ok(Coordinates {
+                latitude: -(33 * 3600 + 52 * 60),
+                longitude: 151 * 3600 + 13 * 60,
+            })
+        );
+        for invalid in [
+            "",
+            "+4042",
+            "4042-07400",
+            "+4042-0740",
+            "+4060-07400",
+            "+9100-07400",
+            "+4042-18100",
+            "+4042+-7400",
+        ] {
+            assert_eq!(
+                invalid.parse::<Coordinates>(),
+                Err(Error::InvalidCoordinates(invalid.to_string()))
+            );
+        }
+    }
+}
@@ -16,7 +16,10 @@
 //! (currently 1800..2100), rather than having an iterator that produces
 //! timespans indefinitely. Not only do we need a complete set of timespans
 //! for sorting, but it is not necessarily advisable to rely on offset
-//! changes so far into the future!
+//! changes so far into the future! Instead, the rule that keeps producing
+//! transitions after the last one in the set can be obtained separately as
+//! an `ExtraRule`, which mirrors the POSIX TZ string footer that `zic`
+//! writes at the end of compiled files.
 //!
 //! ### Example
 //!
@@ -101,6 +104,9 @@
 //! The logic in this file is based off of `zic.c`, which comes with the
 //! zoneinfo files and is in the public domain.
 
+use std::fmt;
+
+use crate::line::{DaySpec, Month, TimeType, Year};
 use crate::table::{RuleInfo, Saving, Table, ZoneInfo};
 
 /// A set of timespans, separated by the instances at which the timespans
@@ -108,7 +114,7 @@ use crate::table::{RuleInfo, Saving, Table, ZoneInfo};
 ///
 /// This mimics the `FixedTimespanSet` struct in `datetime::cal::zone`,
 /// except it uses owned `Vec`s instead of slices.
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 pub struct FixedTimespanSet {
     /// The first timespan, which is assumed to have been in effect up until
     /// the initial transition instant (if any). Each set has to have at
@@ -128,7 +134,7 @@ pub struct FixedTimespanSet {
 /// This mimics the `FixedTimespan` struct in `datetime::cal::zone`, except
 /// instead of “total offset” and “is DST” fields, it has separate UTC and
 /// DST fields. Also, the name is an owned `String` here instead of a slice.
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 pub struct FixedTimespan {
     /// The number of seconds offset from UTC during this timespan.
     pub utc_offset: i64,
@@ -147,11 +153,141 @@ impl FixedTimespan {
     }
 }
 
+/// The rule that keeps generating timespans for a time zone after the last
+/// transition in its `FixedTimespanSet`.
+///
+/// This is the same information as the POSIX TZ string that `zic` appends to
+/// compiled files: a standard timespan, and optionally a daylight-saving
+/// timespan together with the yearly instants at which it starts and ends.
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+pub struct ExtraRule {
+    /// The timespan in effect outside of daylight-saving time.
+    pub standard: FixedTimespan,
+
+    /// The daylight-saving timespan, if the zone still observes one.
+    pub daylight: Option<DaylightRule>,
+}
+
+/// The daylight-saving part of an `ExtraRule`.
+///
+/// As in `zic`, the daylight-saving timespan is the one with a non-zero
+/// `dst_offset`, even if that offset is negative (such as winter time in
+/// `Europe/Dublin`).
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+pub struct DaylightRule {
+    /// The timespan in effect between `start` and `end`.
+    pub timespan: FixedTimespan,
+
+    /// When the daylight-saving timespan begins each year, in the local time
+    /// of the standard timespan.
+    pub start: RuleTransition,
+
+    /// When the daylight-saving timespan ends each year, in the local time
+    /// of the daylight-saving timespan.
+    pub end: RuleTransition,
+}
+
+/// A yearly transition instant, as written in a POSIX TZ string.
+#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone)]
+pub struct RuleTransition {
+    /// The day of the year on which the transition happens.
+    pub date: RuleDate,
+
+    /// The local time of the transition, as the number of seconds after
+    /// midnight. This can be negative, or more than 24 hours, when the rule
+    /// is not naturally expressed as a weekday of the month.
+    pub time: i64,
+}
+
+/// A day of the year, in one of the forms allowed by POSIX TZ strings.
+#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone)]
+pub enum RuleDate {
+    /// `Jn`: the one-based day of the year, never counting February 29th.
+    JulianDay(u16),
+
+    /// `n`: the zero-based day of the year, counting February 29th in leap
+    /// years.
+    DayOfYear(u16),
+
+    /// `Mm.w.d`: weekday `d` (0 is Sunday) of week `w` of month `m`, where
+    /// week 5 means the last such weekday in the month.
+    MonthWeekday { month: u8, week: u8, weekday: u8 },
+}
+
+/// Formats the rule as a POSIX TZ string, such as `EST5EDT,M3.2.0,M11.1.0`, in the
+/// same way as the footer that `zic` writes.
+impl fmt::Display for ExtraRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // POSIX counts offsets in hours west of Greenwich, so the sign is flipped.
+        write_name(f, &self.standard.name)?;
+        write_time(f, -self.standard.total_offset())?;
+        let daylight = match &self.daylight {
+            Some(daylight) => daylight,
+            None => return Ok(()),
+        };
+
+        write_name(f, &daylight.timespan.name)?;
+        if daylight.timespan.total_offset() != self.standard.total_offset() + 3600 {
+            write_time(f, -daylight.timespan.total_offset())?;
+        }
+        write!(f, ",{},{}", daylight.start, daylight.end)
+    }
+}
+
+impl fmt::Display for RuleTransition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.date {
+            RuleDate::JulianDay(day) => write!(f, "J{day}")?,
+            RuleDate::DayOfYear(day) => write!(f, "{day}")?,
+            RuleDate::MonthWeekday {
+                month,
+                week,
+                weekday,
+            } => write!(f, "M{month}.{week}.{weekday}")?,
+        }
+        // Transitions are at 02:00 unless the string says otherwise.
+        if self.time != 7200 {
+            f.write_str("/")?;
+            write_time(f, self.time)?;
+        }
+        Ok(())
+    }
+}
+
+/// Names that aren't made of at least three letters, such as `+03`, need quoting.
+fn write_name(f: &mut fmt::Formatter<'_>, name: &str) -> fmt::Result {
+    if name.len() >= 3 && name.bytes().all(|c| c.is_ascii_alphabetic()) {
+        f.write_str(name)
+    } else {
+        write!(f, "<{name}>")
+    }
+}
+
+/// Writes `[-]hh[:mm[:ss]]`, leaving out minutes and seconds when they are zero.
+fn write_time(f: &mut fmt::Formatter<'_>, seconds: i64) -> fmt::Result {
+    if seconds < 0 {
+        f.write_str("-")?;
+    }
+    let seconds = seconds.abs();
+    write!(f, "{}", seconds / 3600)?;
+    match (seconds / 60 % 60, seconds % 60) {
+        (0, 0) => Ok(()),
+        (minutes, 0) => write!(f, ":{minutes:02}"),
+        (minutes, seconds) => write!(f, ":{minutes:02}:{seconds:02}"),
+    }
+}
+
 /// Trait to put the `timespans` method on Tables.
 pub trait TableTransitions {
     /// Computes a fixed timespan set for the timezone with the given name.
     /// Returns `None` if the table doesn’t contain a time zone with that name.
     fn timespans(&self, zone_name: &str) -> Option<FixedTimespanSet>;
+
+    /// Computes the rule that applies after the last transition of the
+    /// timezone with the given name. Returns `None` if the table doesn’t
+    /// contain a time zone with that name, or if its current rules can’t be
+    /// reduced to at most one standard and one daylight-saving timespan.
+    fn extra_rule(&self, zone_name: &str) -> Option<ExtraRule>;
 }
 
 impl TableTransitions for Table {
@@ -233,8 +369,157 @@ impl TableTransitions for Table {
 
         Some(builder.build())
     }
+
+    fn extra_rule(&self, zone_name: &str) -> Option<ExtraRule> {
+        let zone_info = self.get_zoneset(zone_name)?.last()?;
+        let utc_offset = zone_info.offset;
+
+        let rules = match zone_info.saving {
+            Saving::NoSaving | Saving::OneOff(_) => {
+                let dst_offset = match zone_info.saving {
+                    Saving::OneOff(amount) => amount,
+                    _ => 0,
+                };
+                return Some(ExtraRule {
+                    standard: FixedTimespan {
+                        utc_offset,
+                        dst_offset,
+                        name: zone_info.format.format(utc_offset, dst_offset, None),
+                    },
+                    daylight: None,
+                });
+            }
+            Saving::Multiple(ref rules) => &self.rulesets[rules],
+        };
+
+        // Like `zic`, only the rules that apply until the end of time are
+        // taken into account. If there are none, the last timespan lasts
+        // forever.
+        let ongoing = rules
+            .iter()
+            .filter(|r| r.to_year == Some(Year::Maximum))
+            .collect::<Vec<_>>();
+        let (std_rule, dst_rule) = match ongoing[..] {
+            [] => {
+                let timespans = self.timespans(zone_name)?;
+                let standard = match timespans.rest.last() {
+                    Some((_, timespan)) => timespan.clone(),
+                    None => timespans.first,
+                };
+                return Some(ExtraRule {
+                    standard,
+                    daylight: None,
+                });
+            }
+            [a, b] if a.time_to_add == 0 && b.time_to_add != 0 => (a, b),
+            [a, b] if a.time_to_add != 0 && b.time_to_add == 0 => (b, a),
+            _ => return None,
+        };
+
+        let save = dst_rule.time_to_add;
+        Some(ExtraRule {
+            standard: FixedTimespan {
+                utc_offset,
+                dst_offset: 0,
+                name: zone_info
+                    .format
+                    .format(utc_offset, 0, std_rule.letters.as_ref()),
+            },
+            daylight: Some(DaylightRule {
+                timespan: FixedTimespan {
+                    utc_offset,
+                    dst_offset: save,
+                    name: zone_info
+                        .format
+                        .format(utc_offset, save, dst_rule.letters.as_ref()),
+                },
+                start: rule_transition(dst_rule, save, utc_offset)?,
+                end: rule_transition(std_rule, save, utc_offset)?,
+            }),
+        })
+    }
 }
 
+/// Converts a rule into the local time at which it takes effect, measured in
+/// the timespan that it ends. This follows `stringrule` in `zic.c`.
+fn rule_transition(rule: &RuleInfo, save: i64, utc_offset: i64) -> Option<RuleTransition> {
+    let mut time = rule.time;
+    if rule.time_type == TimeType::UTC {
+        time += utc_offset;
+    }
+    if rule.time_type != TimeType::Wall && rule.time_to_add == 0 {
+        time += save;
+    }
+
+    let month = rule.month as u8;
+    let date = match rule.day {
+        DaySpec::Ordinal(day) => {
+            if rule.month == Month::February && day == 29 {
+                return None;
+            }
+            let days_before = (1..month)
+                .map(|m| MONTHS[m as usize - 1].length(false) as u16)
+                .sum::<u16>();
+            // `zic` omits the "J" in January and February, as that’s shorter.
+            if month <= Month::February as u8 {
+                RuleDate::DayOfYear(days_before + day as u16 - 1)
+            } else {
+                RuleDate::JulianDay(days_before + day as u16)
+            }
+        }
+        DaySpec::Last(weekday) => RuleDate::MonthWeekday {
+            month,
+            week: 5,
+            weekday: weekday as u8,
+        },
+        DaySpec::FirstOnOrAfter(weekday, day) => {
+            let offset = (day as i64 - 1) % 7;
+            time += offset * 24 * 60 * 60;
+            RuleDate::MonthWeekday {
+                month,
+                week: 1 + (day as u8 - 1) / 7,
+                weekday: (weekday as i64 - offset).rem_euclid(7) as u8,
+            }
+        }
+        DaySpec::LastOnOrBefore(weekday, day) if day == rule.month.length(true) => {
+            RuleDate::MonthWeekday {
+                month,
+                week: 5,
+                weekday: weekday as u8,
+            }
+        }
+        DaySpec::LastOnOrBefore(weekday, day) => {
+            if day < 7 {
+                return None;
+            }
+            let offset = day as i64 % 7;
+            time += offset * 24 * 60 * 60;
+            RuleDate::MonthWeekday {
+                month,
+                week: day as u8 / 7,
+                weekday: (weekday as i64 - offset).rem_euclid(7) as u8,
+            }
+        }
+    };
+
+    Some(RuleTransition { date, time })
+}
+
+const MONTHS: [Month; 12] = [
+    Month::January,
+    Month::February,
+    Month::March,
+    Month::April,
+    Month::May,
+    Month::June,
+    Month::July,
+    Month::August,
+    Month::September,
+    Month::October,
+    Month::November,
+    Month::December,
+];
+
 #[derive(Debug, Default)]
 struct FixedTimespanSetBuilder {
     first: Option<FixedTimespan>,
@@ -378,7 +663,7 @@ impl FixedTimespanSetBuilder {
     }
 
     fn build(mut self) -> FixedTimespanSet {
-        self.rest.sort_by(|a, b| a.0.cmp(&b.0));
+        self.rest.sort_by_key(|a| a.0);
 
         let first = match self.first {
             Some(ft) => ft,
@@ -2,7 +2,10 @@ extern crate parse_zoneinfo;
 
 use parse_zoneinfo::line::{ChangeTime, DaySpec, Line, Month, TimeSpec, TimeType, Weekday, Year};
 use parse_zoneinfo::table::{Format, RuleInfo, Saving, Table, TableBuilder, ZoneInfo};
-use parse_zoneinfo::transitions::{FixedTimespan, FixedTimespanSet, TableTransitions};
+use parse_zoneinfo::transitions::{
+    DaylightRule, ExtraRule, FixedTimespan, FixedTimespanSet, RuleDate, RuleTransition,
+    TableTransitions,
+};
 
 #[test]
 fn no_transitions() {
@@ -818,3 +821,162 @@ Zone    Asia/Dushanbe   4:35:12 -   LMT 1924 May  2
     let table = table.build();
     let _ = table.timespans("Asia/Dushanbe").unwrap();
 }
+
+fn build_table(zoneinfo: &str) -> Table {
+    let mut table = TableBuilder::new();
+    for line in zoneinfo.lines() {
+        table.add_line(Line::new(line).unwrap()).unwrap();
+    }
+    table.build()
+}
+
+#[test]
+fn extra_rule_new_york() {
+    let table = build_table(
+        r#"
+Rule    US  1967    2006    -   Oct lastSun 2:00    0   S
+Rule    US  1967    1973    -   Apr lastSun 2:00    1:00    D
+Rule    US  2007    max -   Mar Sun>=8  2:00    1:00    D
+Rule    US  2007    max -   Nov Sun>=1  2:00    0   S
+Zone America/New_York   -4:56:02 -  LMT 1883 Nov 18 17:00u
+            -5:00   US  E%sT
+"#,
+    );
+
+    assert_eq!(
+        table.extra_rule("America/New_York"),
+        Some(ExtraRule {
+            standard: FixedTimespan {
+                utc_offset: -18000,
+                dst_offset: 0,
+                name: "EST".to_owned()
+            },
+            daylight: Some(DaylightRule {
+                timespan: FixedTimespan {
+                    utc_offset: -18000,
+                    dst_offset: 3600,
+                    name: "EDT".to_owned()
+                },
+                start: RuleTransition {
+                    date: RuleDate::MonthWeekday {
+                        month: 3,
+                        week: 2,
+                        weekday: 0
+                    },
+                    time: 7200,
+                },
+                end: RuleTransition {
+                    date: RuleDate::MonthWeekday {
+                        month: 11,
+                        week: 1,
+                        weekday: 0
+                    },
+                    time: 7200,
+                },
+            }),
+        })
+    );
+}
+
+#[test]
+fn extra_rule_negative_dst_and_shifted_weekdays() {
+    let table = build_table(
+        r#"
+Rule    Eire    1981    max -   Mar lastSun 1:00u   0   -
+Rule    Eire    1996    max -   Oct lastSun 1:00u   -1:00   -
+Zone    Europe/Dublin   1:00    Eire    IST/GMT
+Rule Palestine  2059    max -   Mar Sat<=30 2:00    1:00    S
+Rule Palestine  2072    max -   Oct Sat<=30 2:00    0   -
+Zone    Asia/Gaza   2:00    Palestine   EE%sT
+"#,
+    );
+
+    let dublin = table.extra_rule("Europe/Dublin").unwrap();
+    assert_eq!(dublin.standard.name, "IST");
+    let daylight = dublin.daylight.unwrap();
+    assert_eq!(daylight.timespan.dst_offset, -3600);
+    assert_eq!(daylight.start.time, 7200);
+    assert_eq!(daylight.end.time, 3600);
+
+    // `Sat<=30` becomes the Thursday of the fourth week, two days later.
+    let gaza = table.extra_rule("Asia/Gaza").unwrap().daylight.unwrap();
+    let thursday_of_week_four = |month| RuleDate::MonthWeekday {
+        month,
+        week: 4,
+        weekday: 4,
+    };
+    assert_eq!(
+        gaza.start,
+        RuleTransition {
+            date: thursday_of_week_four(3),
+            time: 50 * 3600
+        }
+    );
+    assert_eq!(
+        gaza.end,
+        RuleTransition {
+            date: thursday_of_week_four(10),
+            time: 50 * 3600
+        }
+    );
+}
+
+#[test]
+fn extra_rule_without_daylight_saving() {
+    let table = build_table(
+        r#"
+Rule    Japan   1948    only    -   May Sat>=1  24:00   1:00    D
+Rule    Japan   1948    1951    -   Sep Sat>=8  25:00   0   S
+Zone    Asia/Tokyo  9:18:59 -   LMT 1887 Dec 31 15:00u
+            9:00    Japan   J%sT
+"#,
+    );
+
+    assert_eq!(
+        table.extra_rule("Asia/Tokyo"),
+        Some(ExtraRule {
+            standard: FixedTimespan {
+                utc_offset: 32400,
+                dst_offset: 0,
+                name: "JST".to_owned()
+            },
+            daylight: None,
+        })
+    );
+    assert_eq!(table.extra_rule("Asia/Osaka"), None);
+}
+
+#[test]
+fn extra_rule_tz_strings() {
+    let table = build_table(
+        r#"
+Rule    US  2007    max -   Mar Sun>=8  2:00    1:00    D
+Rule    US  2007    max -   Nov Sun>=1  2:00    0   S
+Zone America/New_York   -5:00   US  E%sT
+Rule    Eire    1981    max -   Mar lastSun 1:00u   0   -
+Rule    Eire    1996    max -   Oct lastSun 1:00u   -1:00   -
+Zone    Europe/Dublin   1:00    Eire    IST/GMT
+Rule Palestine  2059    max -   Mar Sat<=30 2:00    1:00    S
+Rule Palestine  2072    max -   Oct Sat<=30 2:00    0   -
+Zone    Asia/Gaza   2:00    Palestine   EE%sT
+Rule    LH  2008    max -   Apr Sun>=1  2:00    0   -
+Rule    LH  2008    max -   Oct Sun>=1  2:00    0:30    -
+Zone Australia/Lord_Howe 10:30  LH  %z
+Zone    Asia/Tokyo  9:00    -   JST
+Zone    America/Sao_Paulo   -3:00   -   %z
+Zone    Asia/Kolkata    5:30    -   IST
+"#,
+    );
+
+    let tz_string = |zone| table.extra_rule(zone).unwrap().to_string();
+    assert_eq!(tz_string("America/New_York"), "EST5EDT,M3.2.0,M11.1.0");
+    assert_eq!(tz_string("Europe/Dublin"), "IST-1GMT0,M10.5.0,M3.5.0/1");
+    assert_eq!(tz_string("Asia/Gaza"), "EET-2EEST,M3.4.4/50,M10.4.4/50");
+    assert_eq!(
+        tz_string("Australia/Lord_Howe"),
+        "<+1030>-10:30<+11>-11,M10.1.0,M4.1.0"
+    );
+    assert_eq!(tz_string("Asia/Tokyo"), "JST-9");
+    assert_eq!(tz_string("America/Sao_Paulo"), "<-03>3");
+    assert_eq!(tz_string("Asia/Kolkata"), "IST-5:30");
+}